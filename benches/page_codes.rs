@@ -0,0 +1,65 @@
+//! Page code encoding benchmark
+//!
+//! Compares the code page lookups with the `HashMap<char, u8>` tables used previously,
+//! and measures the encoding of a long report.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use escpos::{driver::ConsoleDriver, printer::Printer, utils::*};
+use std::collections::HashMap;
+
+/// Report of 500 lines with accented characters and symbols
+fn report() -> String {
+    (0..500)
+        .map(|i| format!("{i:>4} Crème brûlée à la française   {:>6.2} €\n", i as f32 * 1.25))
+        .collect()
+}
+
+fn lookup(c: &mut Criterion) {
+    let text = report();
+    let code_page = CodePage::from(PageCode::PC858);
+    let table = code_page.chars().collect::<HashMap<_, _>>();
+
+    let mut group = c.benchmark_group("lookup");
+    group.bench_function("code page", |b| {
+        b.iter(|| black_box(&text).chars().filter_map(|c| code_page.byte(c)).count())
+    });
+    group.bench_function("hash map", |b| {
+        b.iter(|| black_box(&text).chars().filter_map(|c| table.get(&c)).count())
+    });
+    group.finish();
+
+    let bytes = text.chars().filter_map(|c| code_page.byte(c)).collect::<Vec<_>>();
+    let mut group = c.benchmark_group("reverse lookup");
+    group.bench_function("code page", |b| {
+        b.iter(|| {
+            black_box(&bytes)
+                .iter()
+                .filter_map(|&byte| code_page.char(byte))
+                .count()
+        })
+    });
+    group.bench_function("hash map", |b| {
+        b.iter(|| {
+            black_box(&bytes)
+                .iter()
+                .filter_map(|&byte| table.iter().find(|(_, &b)| b == byte).map(|(&c, _)| c))
+                .count()
+        })
+    });
+    group.finish();
+}
+
+fn write(c: &mut Criterion) {
+    let text = report();
+
+    c.bench_function("write report", |b| {
+        b.iter(|| {
+            Printer::new(ConsoleDriver::open(false), Protocol::default(), Some(PageCode::PC858))
+                .write(black_box(&text))
+                .map(|_| ())
+        })
+    });
+}
+
+criterion_group!(benches, lookup, write);
+criterion_main!(benches);
@@ -0,0 +1,25 @@
+use escpos::utils::*;
+use std::{env, fs};
+
+fn main() {
+    env_logger::init();
+
+    // Decode a file captured with `FileDriver` or a network sniffer,
+    // e.g. `cargo run --example decoder -- ./job.bin`
+    let data = match env::args().nth(1) {
+        Some(path) => fs::read(path).expect("cannot read file"),
+        None => vec![
+            27, 64, 27, 116, 19, 27, 69, 1, b'T', b'o', b't', b'a', b'l', b' ', b'1', b'0', 0xD5, 10, 27, 69, 0, 29,
+            86, 65, 0,
+        ],
+    };
+
+    for instruction in Decoder::new(None).decode(&data) {
+        println!(
+            "{:06X}  {:<24} {:?}",
+            instruction.offset,
+            format!("{:02X?}", instruction.bytes.iter().take(6).collect::<Vec<_>>()),
+            instruction.command
+        );
+    }
+}
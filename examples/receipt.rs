@@ -2,8 +2,6 @@ use escpos::printer::Printer;
 use escpos::utils::*;
 use escpos::{driver::*, errors::Result};
 
-const CHARS_BY_LINE: usize = 42;
-const EURO: &[u8] = &[0xD5]; // €
 const NUM: &[u8] = &[0xF8]; // °
 
 fn main() -> Result<()> {
@@ -30,45 +28,49 @@ fn main() -> Result<()> {
 
     // let driver = NetworkDriver::open("192.168.1.248", 9100, None)?;
     let driver = ConsoleDriver::open(true);
-    let mut printer = Printer::new(driver, Protocol::default(), None);
-    printer.init()?.justify(JustifyMode::CENTER)?;
+    let profile = PrinterProfile::from(PrinterModel::EpsonTmT88);
+    let columns = profile.columns(Font::A).unwrap_or(42) as usize;
+    let mut printer = Printer::new(driver, Protocol::default(), Some(PageCode::PC858));
+    printer.profile(Some(profile)).init()?.justify(JustifyMode::CENTER)?;
 
     // Logo
     #[cfg(feature = "graphics")]
     printer.bit_image("./resources/images/rust-logo-small.png")?;
 
     // Name + address
+    let big = Style::new().with_size(2, 2);
     printer
-        .bold(true)?
-        .size(2, 2)?
-        .writeln("My Shop")?
-        .reset_size()?
-        .bold(false)?
+        .with_style(big.with_bold(true), |printer| printer.writeln("My Shop"))?
         .writeln("1, rue des gloutons")?
         .writeln("75000 Paris")?
         .feed()?
         .justify(JustifyMode::LEFT)?
         .writeln("2023-11-13 13:22")?
-        .writeln("-".repeat(42).as_str())?
+        .writeln("-".repeat(columns).as_str())?
         .write("Ticket n")?
         .custom_with_page_code(NUM, PageCode::PC858)?
-        .size(2, 2)?
-        .writeln("23")?
-        .reset_size()?
-        .writeln("-".repeat(42).as_str())?;
+        .with_style(big, |printer| printer.writeln("23"))?
+        .writeln("-".repeat(columns).as_str())?;
 
     // Items
+    let table = Table::new(vec![
+        Column::new(ColumnWidth::Fixed(2)).with_align(ColumnAlign::Right),
+        Column::new(ColumnWidth::Proportional(1)),
+        Column::new(ColumnWidth::Fixed(9)).with_align(ColumnAlign::Decimal(2)),
+        Column::new(ColumnWidth::Fixed(1)),
+    ]);
     for item in items {
-        item.print(&mut printer, 1)?;
+        item.print(&mut printer, &table)?;
     }
 
     // Total
-    printer.writeln("-".repeat(42).as_str())?;
-    subtotal.print(&mut printer, 1)?;
-    tax.print(&mut printer, 1)?;
-    printer.size(2, 2)?;
-    total.print(&mut printer, 2)?;
-    printer.reset_size()?;
+    printer.writeln("-".repeat(columns).as_str())?;
+    subtotal.print(&mut printer, &table)?;
+    tax.print(&mut printer, &table)?;
+    printer.with_style(big, |printer| {
+        total.print(printer, &table)?;
+        Ok(printer)
+    })?;
 
     printer.print_cut()?;
 
@@ -94,30 +96,10 @@ impl Item {
         }
     }
 
-    fn print<D: Driver>(&self, printer: &mut Printer<D>, size: u8) -> Result<()> {
-        // Length of characters
-        let mut characters_length = self.name.len() + self.price.to_string().len() + 3;
-        if self.quantity.is_some() {
-            characters_length += 2;
-        }
-        if self.symbol {
-            characters_length += 2;
-        }
-        characters_length *= size as usize;
-
-        // Number of spaces between name and price
-        let spaces = " ".repeat((CHARS_BY_LINE - characters_length) / size as usize);
-
-        // Print item
-        if let Some(quantity) = self.quantity {
-            printer.write(&format!("{} ", quantity))?;
-        }
-        printer.write(&format!("{}{}{:.2}", self.name, spaces, self.price))?;
-        if self.symbol {
-            printer.write(" ")?;
-            printer.custom_with_page_code(EURO, PageCode::PC858)?;
-        }
-        printer.feed()?;
+    fn print<D: Driver>(&self, printer: &mut Printer<D>, table: &Table) -> Result<()> {
+        let quantity = self.quantity.map(|quantity| quantity.to_string()).unwrap_or_default();
+        let symbol = if self.symbol { "€" } else { "" };
+        printer.table_row(table, &[&quantity, &self.name, &format!("{:.2}", self.price), symbol])?;
 
         Ok(())
     }
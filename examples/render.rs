@@ -0,0 +1,37 @@
+use escpos::printer::Printer;
+use escpos::utils::*;
+use escpos::{driver::*, errors::Result};
+
+fn main() -> Result<()> {
+    env_logger::init();
+
+    // 80 mm paper at 203 dpi
+    let driver = RenderDriver::open(RenderOption::new(576, 203, Some(PageCode::PC858))?);
+    Printer::new(driver.clone(), Protocol::default(), Some(PageCode::PC858))
+        .debug_mode(Some(DebugMode::Dec))
+        .init()?
+        .justify(JustifyMode::CENTER)?
+        .bold(true)?
+        .size(2, 2)?
+        .writeln("My Shop")?
+        .reset_size()?
+        .bold(false)?
+        .writeln("1, rue des gloutons")?
+        .feed()?
+        .justify(JustifyMode::LEFT)?
+        .underline(UnderlineMode::Single)?
+        .writeln("Item                          Price")?
+        .underline(UnderlineMode::None)?
+        .writeln("Macbook Pro                 2500.00€")?
+        .reverse(true)?
+        .writeln("Total                       2500.00€")?
+        .reverse(false)?
+        .feed()?
+        .justify(JustifyMode::CENTER)?
+        .bit_image("./resources/images/rust-logo-small.png")?
+        .print_cut()?;
+
+    driver.save("./receipt.png")?;
+
+    Ok(())
+}
@@ -0,0 +1,225 @@
+//! Right-to-left text: Arabic shaping and bidirectional reordering
+
+use unicode_bidi::BidiInfo;
+
+/// Arabic letters with their presentation forms (isolated, final, initial, medial)
+///
+/// Letters without initial form only join the previous letter, letters without final form never join.
+const ARABIC_FORMS: [(char, [char; 4]); 42] = [
+    ('\u{0621}', ['\u{FE80}', '\0', '\0', '\0']),                   // Hamza
+    ('\u{0622}', ['\u{FE81}', '\u{FE82}', '\0', '\0']),             // Alef with madda above
+    ('\u{0623}', ['\u{FE83}', '\u{FE84}', '\0', '\0']),             // Alef with hamza above
+    ('\u{0624}', ['\u{FE85}', '\u{FE86}', '\0', '\0']),             // Waw with hamza above
+    ('\u{0625}', ['\u{FE87}', '\u{FE88}', '\0', '\0']),             // Alef with hamza below
+    ('\u{0626}', ['\u{FE89}', '\u{FE8A}', '\u{FE8B}', '\u{FE8C}']), // Yeh with hamza above
+    ('\u{0627}', ['\u{FE8D}', '\u{FE8E}', '\0', '\0']),             // Alef
+    ('\u{0628}', ['\u{FE8F}', '\u{FE90}', '\u{FE91}', '\u{FE92}']), // Beh
+    ('\u{0629}', ['\u{FE93}', '\u{FE94}', '\0', '\0']),             // Teh marbuta
+    ('\u{062A}', ['\u{FE95}', '\u{FE96}', '\u{FE97}', '\u{FE98}']), // Teh
+    ('\u{062B}', ['\u{FE99}', '\u{FE9A}', '\u{FE9B}', '\u{FE9C}']), // Theh
+    ('\u{062C}', ['\u{FE9D}', '\u{FE9E}', '\u{FE9F}', '\u{FEA0}']), // Jeem
+    ('\u{062D}', ['\u{FEA1}', '\u{FEA2}', '\u{FEA3}', '\u{FEA4}']), // Hah
+    ('\u{062E}', ['\u{FEA5}', '\u{FEA6}', '\u{FEA7}', '\u{FEA8}']), // Khah
+    ('\u{062F}', ['\u{FEA9}', '\u{FEAA}', '\0', '\0']),             // Dal
+    ('\u{0630}', ['\u{FEAB}', '\u{FEAC}', '\0', '\0']),             // Thal
+    ('\u{0631}', ['\u{FEAD}', '\u{FEAE}', '\0', '\0']),             // Reh
+    ('\u{0632}', ['\u{FEAF}', '\u{FEB0}', '\0', '\0']),             // Zain
+    ('\u{0633}', ['\u{FEB1}', '\u{FEB2}', '\u{FEB3}', '\u{FEB4}']), // Seen
+    ('\u{0634}', ['\u{FEB5}', '\u{FEB6}', '\u{FEB7}', '\u{FEB8}']), // Sheen
+    ('\u{0635}', ['\u{FEB9}', '\u{FEBA}', '\u{FEBB}', '\u{FEBC}']), // Sad
+    ('\u{0636}', ['\u{FEBD}', '\u{FEBE}', '\u{FEBF}', '\u{FEC0}']), // Dad
+    ('\u{0637}', ['\u{FEC1}', '\u{FEC2}', '\u{FEC3}', '\u{FEC4}']), // Tah
+    ('\u{0638}', ['\u{FEC5}', '\u{FEC6}', '\u{FEC7}', '\u{FEC8}']), // Zah
+    ('\u{0639}', ['\u{FEC9}', '\u{FECA}', '\u{FECB}', '\u{FECC}']), // Ain
+    ('\u{063A}', ['\u{FECD}', '\u{FECE}', '\u{FECF}', '\u{FED0}']), // Ghain
+    ('\u{0641}', ['\u{FED1}', '\u{FED2}', '\u{FED3}', '\u{FED4}']), // Feh
+    ('\u{0642}', ['\u{FED5}', '\u{FED6}', '\u{FED7}', '\u{FED8}']), // Qaf
+    ('\u{0643}', ['\u{FED9}', '\u{FEDA}', '\u{FEDB}', '\u{FEDC}']), // Kaf
+    ('\u{0644}', ['\u{FEDD}', '\u{FEDE}', '\u{FEDF}', '\u{FEE0}']), // Lam
+    ('\u{0645}', ['\u{FEE1}', '\u{FEE2}', '\u{FEE3}', '\u{FEE4}']), // Meem
+    ('\u{0646}', ['\u{FEE5}', '\u{FEE6}', '\u{FEE7}', '\u{FEE8}']), // Noon
+    ('\u{0647}', ['\u{FEE9}', '\u{FEEA}', '\u{FEEB}', '\u{FEEC}']), // Heh
+    ('\u{0648}', ['\u{FEED}', '\u{FEEE}', '\0', '\0']),             // Waw
+    ('\u{0649}', ['\u{FEEF}', '\u{FEF0}', '\0', '\0']),             // Alef maksura
+    ('\u{064A}', ['\u{FEF1}', '\u{FEF2}', '\u{FEF3}', '\u{FEF4}']), // Yeh
+    ('\u{067E}', ['\u{FB56}', '\u{FB57}', '\u{FB58}', '\u{FB59}']), // Peh
+    ('\u{0686}', ['\u{FB7A}', '\u{FB7B}', '\u{FB7C}', '\u{FB7D}']), // Tcheh
+    ('\u{0698}', ['\u{FB8A}', '\u{FB8B}', '\0', '\0']),             // Jeh
+    ('\u{06A9}', ['\u{FB8E}', '\u{FB8F}', '\u{FB90}', '\u{FB91}']), // Keheh
+    ('\u{06AF}', ['\u{FB92}', '\u{FB93}', '\u{FB94}', '\u{FB95}']), // Gaf
+    ('\u{06CC}', ['\u{FBFC}', '\u{FBFD}', '\u{FBFE}', '\u{FBFF}']), // Farsi yeh
+];
+
+/// Lam-alef ligatures (alef, isolated, final)
+const LAM_ALEF: [(char, char, char); 4] = [
+    ('\u{0622}', '\u{FEF5}', '\u{FEF6}'),
+    ('\u{0623}', '\u{FEF7}', '\u{FEF8}'),
+    ('\u{0625}', '\u{FEF9}', '\u{FEFA}'),
+    ('\u{0627}', '\u{FEFB}', '\u{FEFC}'),
+];
+
+const LAM: char = '\u{0644}';
+
+/// Characters mirrored in right-to-left runs
+const MIRRORED: [(char, char); 10] = [
+    ('(', ')'),
+    (')', '('),
+    ('[', ']'),
+    (']', '['),
+    ('{', '}'),
+    ('}', '{'),
+    ('<', '>'),
+    ('>', '<'),
+    ('«', '»'),
+    ('»', '«'),
+];
+
+/// Presentation forms of an Arabic letter
+fn forms(c: char) -> Option<[char; 4]> {
+    ARABIC_FORMS
+        .iter()
+        .find(|(letter, _)| *letter == c)
+        .map(|(_, forms)| *forms)
+}
+
+/// Check if a character is skipped when joining letters (harakat and other marks)
+fn is_transparent(c: char) -> bool {
+    matches!(c, '\u{0610}'..='\u{061A}' | '\u{064B}'..='\u{065F}' | '\u{0670}' | '\u{06D6}'..='\u{06ED}')
+}
+
+/// Check if a character joins the following letter (dual-joining letters, tatweel and ZWJ)
+fn joins_next(c: char) -> bool {
+    matches!(c, '\u{0640}' | '\u{200D}') || forms(c).is_some_and(|forms| forms[2] != '\0')
+}
+
+/// Check if a character joins the preceding letter (right and dual-joining letters, tatweel and ZWJ)
+fn joins_previous(c: char) -> bool {
+    matches!(c, '\u{0640}' | '\u{200D}') || forms(c).is_some_and(|forms| forms[1] != '\0')
+}
+
+/// Shape the Arabic letters into their contextual presentation forms
+///
+/// Only the forms for which `available` returns `true` are used: a missing medial form falls back to the
+/// initial form, a missing final or initial form to the isolated form, and a missing isolated form to the letter.
+pub(crate) fn shape(text: &str, available: impl Fn(char) -> bool) -> String {
+    let chars = text.chars().collect::<Vec<_>>();
+    let previous = |i: usize| chars[..i].iter().rev().find(|c| !is_transparent(**c)).copied();
+    let next = |i: usize| {
+        chars[i + 1..]
+            .iter()
+            .position(|c| !is_transparent(*c))
+            .map(|n| i + 1 + n)
+    };
+
+    let mut shaped = String::with_capacity(text.len());
+    let mut skip = None;
+
+    for (i, &c) in chars.iter().enumerate() {
+        if skip == Some(i) {
+            continue;
+        }
+        let Some(forms) = forms(c) else {
+            shaped.push(c);
+            continue;
+        };
+
+        let joined_previous = previous(i).is_some_and(joins_next) && joins_previous(c);
+        let next = next(i);
+
+        // Lam followed by alef
+        if c == LAM {
+            let ligature = next.and_then(|n| LAM_ALEF.iter().find(|(alef, _, _)| *alef == chars[n]).map(|l| (n, l)));
+            if let Some((n, &(_, isolated, final_form))) = ligature {
+                let ligature = if joined_previous { final_form } else { isolated };
+                if available(ligature) {
+                    shaped.push(ligature);
+                    skip = Some(n);
+                    continue;
+                }
+            }
+        }
+
+        let joined_next = joins_next(c) && next.is_some_and(|n| joins_previous(chars[n]));
+        let candidates = match (joined_previous, joined_next) {
+            (false, false) => [forms[0], c, '\0', '\0'],
+            (true, false) => [forms[1], forms[0], c, '\0'],
+            (false, true) => [forms[2], forms[0], c, '\0'],
+            (true, true) => [forms[3], forms[2], forms[0], c],
+        };
+        let form = candidates
+            .into_iter()
+            .find(|&form| form != '\0' && (form == c || available(form)))
+            .unwrap_or(c);
+        shaped.push(form);
+    }
+
+    shaped
+}
+
+/// Reorder a line from logical to visual order with the Unicode bidirectional algorithm
+///
+/// Returns the reordered line and whether its base direction is right-to-left.
+pub(crate) fn reorder(line: &str) -> (String, bool) {
+    let bidi = BidiInfo::new(line, None);
+    let mut reordered = String::with_capacity(line.len());
+    let rtl = bidi
+        .paragraphs
+        .first()
+        .is_some_and(|paragraph| paragraph.level.is_rtl());
+
+    for paragraph in &bidi.paragraphs {
+        let (levels, runs) = bidi.visual_runs(paragraph, paragraph.range.clone());
+        for run in runs {
+            match levels[run.start].is_rtl() {
+                true => reordered.extend(line[run].chars().rev().map(|c| {
+                    MIRRORED
+                        .iter()
+                        .find(|(from, _)| *from == c)
+                        .map_or(c, |(_, mirrored)| *mirrored)
+                })),
+                false => reordered.push_str(&line[run]),
+            }
+        }
+    }
+
+    (reordered, rtl)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shape() {
+        // بيت: initial beh, medial yeh, final teh
+        assert_eq!(shape("بيت", |_| true), "\u{FE91}\u{FEF4}\u{FE96}");
+        // دار: dal and reh do not join the next letter
+        assert_eq!(shape("دار", |_| true), "\u{FEA9}\u{FE8D}\u{FEAD}");
+        // سلام: lam-alef ligature
+        assert_eq!(shape("سلام", |_| true), "\u{FEB3}\u{FEFC}\u{FEE1}");
+        // Harakat are transparent
+        assert_eq!(shape("بَت", |_| true), "\u{FE91}\u{064E}\u{FE96}");
+        assert_eq!(shape("abc 12", |_| true), "abc 12");
+    }
+
+    #[test]
+    fn test_shape_fallback() {
+        // Only isolated and initial forms available (PC864 like)
+        let available = |c: char| {
+            matches!(
+                c,
+                '\u{FE8F}' | '\u{FE91}' | '\u{FE95}' | '\u{FE97}' | '\u{FEF1}' | '\u{FEF3}'
+            )
+        };
+        assert_eq!(shape("بيت", available), "\u{FE91}\u{FEF3}\u{FE95}");
+        assert_eq!(shape("سلام", |_| false), "سلام");
+    }
+
+    #[test]
+    fn test_reorder() {
+        assert_eq!(reorder("abc"), ("abc".to_string(), false));
+        assert_eq!(reorder("אבג 123"), ("123 גבא".to_string(), true));
+        assert_eq!(reorder("Total: שלום"), ("Total: םולש".to_string(), false));
+        assert_eq!(reorder("(שלום)"), ("(םולש)".to_string(), true));
+    }
+}
@@ -106,7 +106,7 @@ impl BitImage {
     }
 
     /// Create a new image from `DynamicImage`
-    fn from_dynamic_image(img: DynamicImage, option: BitImageOption, path: &str) -> Result<Self> {
+    pub(crate) fn from_dynamic_image(img: DynamicImage, option: BitImageOption, path: &str) -> Result<Self> {
         // Resize image with max width and max height constraints and convert to grayscale
         let mut img = match (option.max_width, option.max_height) {
             (Some(max_width), None) => {
@@ -1,9 +1,11 @@
 //! Character
 
+use crate::errors::PrinterError;
+use encoding_rs::{Encoding, BIG5, EUC_KR, GB18030, SHIFT_JIS};
 use std::fmt;
 
 /// Underline mode
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum UnderlineMode {
     None,
     Single,
@@ -21,7 +23,8 @@ impl fmt::Display for UnderlineMode {
 }
 
 /// Text font
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Font {
     A,
     B,
@@ -38,8 +41,140 @@ impl fmt::Display for Font {
     }
 }
 
+/// Print mode selected at once with `ESC !` (font A or B, bold, double height and width, underline)
+///
+/// # Examples
+///
+/// ```rust
+/// use escpos::utils::*;
+///
+/// let mode = PrintMode::new().with_font(Font::B).with_bold(true).with_double_width(true);
+/// assert_eq!(mode.font(), Font::B);
+/// assert!(mode.double_width());
+/// assert!(!mode.underline());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrintMode {
+    pub(crate) font: Font,
+    pub(crate) bold: bool,
+    pub(crate) double_height: bool,
+    pub(crate) double_width: bool,
+    pub(crate) underline: bool,
+}
+
+impl Default for PrintMode {
+    fn default() -> Self {
+        Self {
+            font: Font::A,
+            bold: false,
+            double_height: false,
+            double_width: false,
+            underline: false,
+        }
+    }
+}
+
+impl PrintMode {
+    /// Create a new print mode (font A, without attribute)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set font (A or B)
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Set bold
+    pub fn with_bold(mut self, enabled: bool) -> Self {
+        self.bold = enabled;
+        self
+    }
+
+    /// Set double height
+    pub fn with_double_height(mut self, enabled: bool) -> Self {
+        self.double_height = enabled;
+        self
+    }
+
+    /// Set double width
+    pub fn with_double_width(mut self, enabled: bool) -> Self {
+        self.double_width = enabled;
+        self
+    }
+
+    /// Set single underline
+    pub fn with_underline(mut self, enabled: bool) -> Self {
+        self.underline = enabled;
+        self
+    }
+
+    /// Get font
+    pub fn font(&self) -> Font {
+        self.font
+    }
+
+    /// Get bold
+    pub fn bold(&self) -> bool {
+        self.bold
+    }
+
+    /// Get double height
+    pub fn double_height(&self) -> bool {
+        self.double_height
+    }
+
+    /// Get double width
+    pub fn double_width(&self) -> bool {
+        self.double_width
+    }
+
+    /// Get underline
+    pub fn underline(&self) -> bool {
+        self.underline
+    }
+
+    /// Text size matching the double height and width
+    pub(crate) fn size(&self) -> (u8, u8) {
+        (1 + u8::from(self.double_width), 1 + u8::from(self.double_height))
+    }
+}
+
+/// Character, background or shading colour (`GS ( N`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextColour {
+    None,
+    Colour1,
+    Colour2,
+    Colour3,
+}
+
+impl fmt::Display for TextColour {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextColour::None => write!(f, "none"),
+            TextColour::Colour1 => write!(f, "colour 1"),
+            TextColour::Colour2 => write!(f, "colour 2"),
+            TextColour::Colour3 => write!(f, "colour 3"),
+        }
+    }
+}
+
+impl From<TextColour> for u8 {
+    fn from(value: TextColour) -> Self {
+        match value {
+            TextColour::None => 48,
+            TextColour::Colour1 => 49,
+            TextColour::Colour2 => 50,
+            TextColour::Colour3 => 51,
+        }
+    }
+}
+
 /// Character page code
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PageCode {
     #[default]
     PC437,
@@ -58,6 +193,7 @@ pub enum PageCode {
     PC866,
     PC852,
     PC858,
+    PC874,
     PC720,
     WPC775,
     PC855,
@@ -82,6 +218,53 @@ pub enum PageCode {
     KZ1048,
 }
 
+impl PageCode {
+    /// List of all the page codes
+    pub fn all() -> Vec<Self> {
+        vec![
+            PageCode::PC437,
+            PageCode::Katakana,
+            PageCode::PC850,
+            PageCode::PC860,
+            PageCode::PC863,
+            PageCode::PC865,
+            PageCode::Hiragana,
+            PageCode::PC851,
+            PageCode::PC853,
+            PageCode::PC857,
+            PageCode::PC737,
+            PageCode::ISO8859_7,
+            PageCode::WPC1252,
+            PageCode::PC866,
+            PageCode::PC852,
+            PageCode::PC858,
+            PageCode::PC874,
+            PageCode::PC720,
+            PageCode::WPC775,
+            PageCode::PC855,
+            PageCode::PC861,
+            PageCode::PC862,
+            PageCode::PC864,
+            PageCode::PC869,
+            PageCode::ISO8859_2,
+            PageCode::ISO8859_15,
+            PageCode::PC1098,
+            PageCode::PC1118,
+            PageCode::PC1119,
+            PageCode::PC1125,
+            PageCode::WPC1250,
+            PageCode::WPC1251,
+            PageCode::WPC1253,
+            PageCode::WPC1254,
+            PageCode::WPC1255,
+            PageCode::WPC1256,
+            PageCode::WPC1257,
+            PageCode::WPC1258,
+            PageCode::KZ1048,
+        ]
+    }
+}
+
 impl fmt::Display for PageCode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -101,6 +284,7 @@ impl fmt::Display for PageCode {
             PageCode::PC866 => write!(f, "PC866"),
             PageCode::PC852 => write!(f, "PC852"),
             PageCode::PC858 => write!(f, "PC858"),
+            PageCode::PC874 => write!(f, "PC874"),
             PageCode::PC720 => write!(f, "PC720"),
             PageCode::WPC775 => write!(f, "WPC775"),
             PageCode::PC855 => write!(f, "PC855"),
@@ -146,6 +330,7 @@ impl From<PageCode> for u8 {
             PageCode::PC866 => 17,
             PageCode::PC852 => 18,
             PageCode::PC858 => 19,
+            PageCode::PC874 => 21,
             PageCode::PC720 => 32,
             PageCode::WPC775 => 33,
             PageCode::PC855 => 34,
@@ -172,9 +357,219 @@ impl From<PageCode> for u8 {
     }
 }
 
-/// Character page code
-#[derive(Debug)]
+impl TryFrom<u8> for PageCode {
+    type Error = PrinterError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(PageCode::PC437),
+            1 => Ok(PageCode::Katakana),
+            2 => Ok(PageCode::PC850),
+            3 => Ok(PageCode::PC860),
+            4 => Ok(PageCode::PC863),
+            5 => Ok(PageCode::PC865),
+            6 => Ok(PageCode::Hiragana),
+            11 => Ok(PageCode::PC851),
+            12 => Ok(PageCode::PC853),
+            13 => Ok(PageCode::PC857),
+            14 => Ok(PageCode::PC737),
+            15 => Ok(PageCode::ISO8859_7),
+            16 => Ok(PageCode::WPC1252),
+            17 => Ok(PageCode::PC866),
+            18 => Ok(PageCode::PC852),
+            19 => Ok(PageCode::PC858),
+            21 => Ok(PageCode::PC874),
+            32 => Ok(PageCode::PC720),
+            33 => Ok(PageCode::WPC775),
+            34 => Ok(PageCode::PC855),
+            35 => Ok(PageCode::PC861),
+            36 => Ok(PageCode::PC862),
+            37 => Ok(PageCode::PC864),
+            38 => Ok(PageCode::PC869),
+            39 => Ok(PageCode::ISO8859_2),
+            40 => Ok(PageCode::ISO8859_15),
+            41 => Ok(PageCode::PC1098),
+            42 => Ok(PageCode::PC1118),
+            43 => Ok(PageCode::PC1119),
+            44 => Ok(PageCode::PC1125),
+            45 => Ok(PageCode::WPC1250),
+            46 => Ok(PageCode::WPC1251),
+            47 => Ok(PageCode::WPC1253),
+            48 => Ok(PageCode::WPC1254),
+            49 => Ok(PageCode::WPC1255),
+            50 => Ok(PageCode::WPC1256),
+            51 => Ok(PageCode::WPC1257),
+            52 => Ok(PageCode::WPC1258),
+            53 => Ok(PageCode::KZ1048),
+            _ => Err(PrinterError::Input(format!("invalid page code number: {value}"))),
+        }
+    }
+}
+
+/// Behavior when a character is missing from the page code table
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum UnmappablePolicy {
+    /// Encode the character with the protocol encoder
+    #[default]
+    Encoder,
+    /// Return an error listing the missing characters with their positions
+    Strict,
+    /// Replace the character by a byte
+    Replace(u8),
+    /// Transliterate the character (`é` → `e`, `€` → `EUR`, `“` → `"`...), `?` if still missing
+    Transliterate,
+}
+
+impl fmt::Display for UnmappablePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnmappablePolicy::Encoder => write!(f, "Encoder"),
+            UnmappablePolicy::Strict => write!(f, "Strict"),
+            UnmappablePolicy::Replace(byte) => write!(f, "Replace with {byte:#04x}"),
+            UnmappablePolicy::Transliterate => write!(f, "Transliterate"),
+        }
+    }
+}
+
+/// Behavior for the control characters (C0, `0x00`-`0x1F`) of the printed text
+///
+/// Control characters in the text could otherwise inject commands (ESC, GS...).
+/// Trusted commands are sent with `Printer::custom`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ControlCharacterPolicy {
+    /// Remove the control characters
+    #[default]
+    Strip,
+    /// Replace the control characters by their caret notation (`^[` for ESC)
+    Escape,
+}
+
+impl fmt::Display for ControlCharacterPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ControlCharacterPolicy::Strip => write!(f, "Strip"),
+            ControlCharacterPolicy::Escape => write!(f, "Escape"),
+        }
+    }
+}
+
+/// Kanji character code system (FS C)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KanjiCodeSystem {
+    Jis,
+    ShiftJis,
+    ShiftJis2004,
+}
+
+impl fmt::Display for KanjiCodeSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KanjiCodeSystem::Jis => write!(f, "JIS"),
+            KanjiCodeSystem::ShiftJis => write!(f, "Shift JIS"),
+            KanjiCodeSystem::ShiftJis2004 => write!(f, "Shift JIS-2004"),
+        }
+    }
+}
+
+impl From<KanjiCodeSystem> for u8 {
+    fn from(value: KanjiCodeSystem) -> Self {
+        match value {
+            KanjiCodeSystem::Jis => 0,
+            KanjiCodeSystem::ShiftJis => 1,
+            KanjiCodeSystem::ShiftJis2004 => 2,
+        }
+    }
+}
+
+/// Double-byte encoding of the text printed in Kanji mode
+///
+/// The encoding must match the character set of the printer model: Shift_JIS for Japanese models,
+/// GB18030 for Simplified Chinese models, Big5 for Traditional Chinese models and EUC-KR for Korean models.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KanjiEncoding {
+    ShiftJis,
+    Gb18030,
+    Big5,
+    EucKr,
+}
+
+impl KanjiEncoding {
+    /// `encoding_rs` codec of the encoding
+    pub fn codec(&self) -> &'static Encoding {
+        match self {
+            KanjiEncoding::ShiftJis => SHIFT_JIS,
+            KanjiEncoding::Gb18030 => GB18030,
+            KanjiEncoding::Big5 => BIG5,
+            KanjiEncoding::EucKr => EUC_KR,
+        }
+    }
+
+    /// Kanji code system to select (only on Japanese models)
+    pub fn code_system(&self) -> Option<KanjiCodeSystem> {
+        match self {
+            KanjiEncoding::ShiftJis => Some(KanjiCodeSystem::ShiftJis),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for KanjiEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.codec().name())
+    }
+}
+
+/// Font used for the characters printed in Unicode mode (FS ( C)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnicodeFont {
+    Ank,
+    Japanese,
+    SimplifiedChinese,
+    TraditionalChinese,
+    Korean,
+    Thai,
+    Vietnamese,
+}
+
+impl fmt::Display for UnicodeFont {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnicodeFont::Ank => write!(f, "ANK"),
+            UnicodeFont::Japanese => write!(f, "Japanese"),
+            UnicodeFont::SimplifiedChinese => write!(f, "Simplified Chinese"),
+            UnicodeFont::TraditionalChinese => write!(f, "Traditional Chinese"),
+            UnicodeFont::Korean => write!(f, "Korean"),
+            UnicodeFont::Thai => write!(f, "Thai"),
+            UnicodeFont::Vietnamese => write!(f, "Vietnamese"),
+        }
+    }
+}
+
+impl From<UnicodeFont> for u8 {
+    fn from(value: UnicodeFont) -> Self {
+        match value {
+            UnicodeFont::Ank => 0,
+            UnicodeFont::Japanese => 11,
+            UnicodeFont::SimplifiedChinese => 20,
+            UnicodeFont::TraditionalChinese => 30,
+            UnicodeFont::Korean => 41,
+            UnicodeFont::Thai => 82,
+            UnicodeFont::Vietnamese => 160,
+        }
+    }
+}
+
+/// Positions replaced by the international character sets (`ESC R`)
+const INTERNATIONAL_POSITIONS: [u8; 12] = [0x23, 0x24, 0x40, 0x5B, 0x5C, 0x5D, 0x5E, 0x60, 0x7B, 0x7C, 0x7D, 0x7E];
+
+/// ASCII characters at the positions replaced by the international character sets
+const ASCII_INTERNATIONAL_CHARACTERS: [char; 12] = ['#', '$', '@', '[', '\\', ']', '^', '`', '{', '|', '}', '~'];
+
+/// International character set
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub enum CharacterSet {
+    #[default]
     USA,
     France,
     Germany,
@@ -206,6 +601,92 @@ pub enum CharacterSet {
     IndiaMarathi,
 }
 
+impl fmt::Display for CharacterSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CharacterSet::USA => write!(f, "USA"),
+            CharacterSet::France => write!(f, "France"),
+            CharacterSet::Germany => write!(f, "Germany"),
+            CharacterSet::UK => write!(f, "UK"),
+            CharacterSet::Denmark1 => write!(f, "Denmark I"),
+            CharacterSet::Sweden => write!(f, "Sweden"),
+            CharacterSet::Italy => write!(f, "Italy"),
+            CharacterSet::Spain1 => write!(f, "Spain I"),
+            CharacterSet::Japan => write!(f, "Japan"),
+            CharacterSet::Norway => write!(f, "Norway"),
+            CharacterSet::Denmark2 => write!(f, "Denmark II"),
+            CharacterSet::Spain2 => write!(f, "Spain II"),
+            CharacterSet::LatinAmerica => write!(f, "Latin America"),
+            CharacterSet::Korea => write!(f, "Korea"),
+            CharacterSet::SloveniaCroatia => write!(f, "Slovenia/Croatia"),
+            CharacterSet::China => write!(f, "China"),
+            CharacterSet::Vietnam => write!(f, "Vietnam"),
+            CharacterSet::Arabia => write!(f, "Arabia"),
+            CharacterSet::IndiaDevanagari => write!(f, "India Devanagari"),
+            CharacterSet::IndiaBengali => write!(f, "India Bengali"),
+            CharacterSet::IndiaTamil => write!(f, "India Tamil"),
+            CharacterSet::IndiaTelugu => write!(f, "India Telugu"),
+            CharacterSet::IndiaAssamese => write!(f, "India Assamese"),
+            CharacterSet::IndiaOriya => write!(f, "India Oriya"),
+            CharacterSet::IndiaKannada => write!(f, "India Kannada"),
+            CharacterSet::IndiaMalayalam => write!(f, "India Malayalam"),
+            CharacterSet::IndiaGujarati => write!(f, "India Gujarati"),
+            CharacterSet::IndiaPunjabi => write!(f, "India Punjabi"),
+            CharacterSet::IndiaMarathi => write!(f, "India Marathi"),
+        }
+    }
+}
+
+impl CharacterSet {
+    /// Characters at the positions replaced by the international character set (`INTERNATIONAL_POSITIONS`)
+    fn international_characters(&self) -> [char; 12] {
+        match self {
+            CharacterSet::France => ['#', '$', 'à', '°', 'ç', '§', '^', '`', 'é', 'ù', 'è', '¨'],
+            CharacterSet::Germany => ['#', '$', '§', 'Ä', 'Ö', 'Ü', '^', '`', 'ä', 'ö', 'ü', 'ß'],
+            CharacterSet::UK => ['£', '$', '@', '[', '\\', ']', '^', '`', '{', '|', '}', '~'],
+            CharacterSet::Denmark1 => ['#', '$', '@', 'Æ', 'Ø', 'Å', '^', '`', 'æ', 'ø', 'å', '~'],
+            CharacterSet::Sweden => ['#', '¤', 'É', 'Ä', 'Ö', 'Å', 'Ü', 'é', 'ä', 'ö', 'å', 'ü'],
+            CharacterSet::Italy => ['#', '$', '@', '°', '\\', 'é', '^', 'ù', 'à', 'ò', 'è', 'ì'],
+            CharacterSet::Spain1 => ['₧', '$', '@', '¡', 'Ñ', '¿', '^', '`', '¨', 'ñ', '}', '~'],
+            CharacterSet::Japan => ['#', '$', '@', '[', '¥', ']', '^', '`', '{', '|', '}', '~'],
+            CharacterSet::Norway => ['#', '¤', 'É', 'Æ', 'Ø', 'Å', 'Ü', 'é', 'æ', 'ø', 'å', 'ü'],
+            CharacterSet::Denmark2 => ['#', '$', 'É', 'Æ', 'Ø', 'Å', 'Ü', 'é', 'æ', 'ø', 'å', 'ü'],
+            CharacterSet::Spain2 => ['#', '$', 'á', '¡', 'Ñ', '¿', 'é', '`', 'í', 'ñ', 'ó', 'ú'],
+            CharacterSet::LatinAmerica => ['#', '$', 'á', '¡', 'Ñ', '¿', 'é', 'ü', 'í', 'ñ', 'ó', 'ú'],
+            CharacterSet::Korea => ['#', '$', '@', '[', '₩', ']', '^', '`', '{', '|', '}', '~'],
+            CharacterSet::SloveniaCroatia => ['#', '$', 'Ž', 'Š', 'Đ', 'Ć', 'Č', 'ž', 'š', 'đ', 'ć', 'č'],
+            CharacterSet::China => ['#', '¥', '@', '[', '\\', ']', '^', '`', '{', '|', '}', '~'],
+            CharacterSet::Vietnam => ['#', '₫', '@', '[', '\\', ']', '^', '`', '{', '|', '}', '~'],
+            _ => ASCII_INTERNATIONAL_CHARACTERS,
+        }
+    }
+
+    /// Byte of a character moved to an ASCII position by the character set (`ä` → `0x7B` for Germany)
+    pub(crate) fn substitute(&self, c: char) -> Option<u8> {
+        if c.is_ascii() {
+            return None;
+        }
+
+        self.international_characters()
+            .iter()
+            .position(|&international| international == c)
+            .map(|i| INTERNATIONAL_POSITIONS[i])
+    }
+
+    /// Whether the character set replaces some ASCII characters
+    pub(crate) fn replaces_ascii(&self) -> bool {
+        self.international_characters() != ASCII_INTERNATIONAL_CHARACTERS
+    }
+
+    /// Whether an ASCII character is replaced by another one in the character set (`[` for Germany)
+    pub(crate) fn overrides(&self, c: char) -> bool {
+        ASCII_INTERNATIONAL_CHARACTERS
+            .iter()
+            .zip(self.international_characters())
+            .any(|(&ascii, international)| ascii == c && international != c)
+    }
+}
+
 impl From<CharacterSet> for u8 {
     fn from(value: CharacterSet) -> Self {
         match value {
@@ -0,0 +1,282 @@
+//! Code page tables
+
+use super::{page_codes, PageCode};
+use crate::errors::{PrinterError, Result};
+use std::{borrow::Cow, fs, path::Path};
+
+/// Code page
+///
+/// Table of the characters encoded by each byte of a page code, with the ESC t number selecting it.
+/// The built-in tables are available with `CodePage::from(PageCode)`. A code page registered on the protocol
+/// (`Protocol::with_code_page`) replaces the built-in one, for printers using other ESC t numbers or glyph layouts.
+///
+/// # Examples
+///
+/// ```rust
+/// use escpos::utils::*;
+///
+/// // PC866 selected with ESC t 59 and `Ё` at 0xF0
+/// let code_page = CodePage::from(PageCode::PC866).with_number(59).with_char(0xF0, 'Ё');
+/// assert_eq!(code_page.number(), 59);
+/// assert_eq!(code_page.byte('Ё'), Some(0xF0));
+/// assert_eq!(code_page.char(0x80), Some('А'));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodePage {
+    page_code: PageCode,
+    number: u8,
+    table: Cow<'static, [char; 256]>,
+    index: Cow<'static, [(char, u8)]>,
+}
+
+impl CodePage {
+    /// Create a new code page from the characters of the 256 bytes (`'\0'` for the bytes without character)
+    ///
+    /// The bytes without character (usually `0x00`-`0x7F`) are encoded as ASCII. Printable characters mapped to
+    /// control bytes (`0x00`-`0x1F` and `0x7F`) are dropped, as they would send printer commands.
+    pub fn new(page_code: PageCode, number: u8, mut chars: [char; 256]) -> Self {
+        for (byte, c) in chars.iter_mut().enumerate() {
+            if !allowed(byte as u8, *c) {
+                *c = '\0';
+            }
+        }
+
+        Self {
+            page_code,
+            number,
+            table: Cow::Owned(chars),
+            index: Cow::Owned(index(&chars).to_vec()),
+        }
+    }
+
+    /// Create a new code page from a mapping (`0x80 0x0410 # CYRILLIC CAPITAL LETTER A` lines)
+    ///
+    /// This is the format of the Unicode mapping tables: each line contains the byte and the Unicode code point
+    /// in hexadecimal. Comments (`#`) and bytes without code point are ignored. A printable character mapped to
+    /// a control byte (`0x00`-`0x1F` and `0x7F`) is an error.
+    pub fn from_mapping(page_code: PageCode, number: u8, mapping: &str) -> Result<Self> {
+        let mut chars = ['\0'; 256];
+
+        for (i, line) in mapping.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            let columns = line.split_whitespace().collect::<Vec<_>>();
+            let invalid = || PrinterError::Input(format!("invalid code page mapping at line {}: {line:?}", i + 1));
+
+            match columns[..] {
+                [] | [_] => (),
+                [byte, code_point] => {
+                    let byte = u8::from_str_radix(hexadecimal(byte), 16).map_err(|_| invalid())?;
+                    let c = u32::from_str_radix(hexadecimal(code_point), 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(invalid)?;
+                    if !allowed(byte, c) {
+                        return Err(PrinterError::Input(format!(
+                            "invalid code page mapping at line {}: {c:?} mapped to the control byte {byte:#04X}",
+                            i + 1
+                        )));
+                    }
+                    chars[byte as usize] = c;
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(Self::new(page_code, number, chars))
+    }
+
+    /// Create a new code page from a mapping file (see `CodePage::from_mapping`)
+    pub fn from_file<P: AsRef<Path>>(page_code: PageCode, number: u8, path: P) -> Result<Self> {
+        Self::from_mapping(page_code, number, &fs::read_to_string(path)?)
+    }
+
+    /// Create a built-in code page from its static table and index
+    pub(crate) const fn builtin(
+        page_code: PageCode,
+        number: u8,
+        table: &'static [char; 256],
+        index: &'static [(char, u8); 256],
+    ) -> Self {
+        Self {
+            page_code,
+            number,
+            table: Cow::Borrowed(table),
+            index: Cow::Borrowed(index),
+        }
+    }
+
+    /// Set the ESC t number
+    pub fn with_number(mut self, number: u8) -> Self {
+        self.number = number;
+        self
+    }
+
+    /// Set the character of a byte (`'\0'` to remove it)
+    ///
+    /// A printable character is ignored for a control byte (`0x00`-`0x1F` and `0x7F`).
+    pub fn with_char(mut self, byte: u8, c: char) -> Self {
+        if !allowed(byte, c) {
+            return self;
+        }
+        self.table.to_mut()[byte as usize] = c;
+        self.index = Cow::Owned(index(&self.table).to_vec());
+        self
+    }
+
+    /// Get page code
+    pub fn page_code(&self) -> PageCode {
+        self.page_code
+    }
+
+    /// Get ESC t number
+    pub fn number(&self) -> u8 {
+        self.number
+    }
+
+    /// Get the character encoded by a byte
+    pub fn char(&self, byte: u8) -> Option<char> {
+        Some(self.table[byte as usize]).filter(|&c| c != '\0')
+    }
+
+    /// Get the byte encoding a character
+    pub fn byte(&self, c: char) -> Option<u8> {
+        match c {
+            '\0' => None,
+            _ => self
+                .index
+                .binary_search_by_key(&c, |&(c, _)| c)
+                .ok()
+                .map(|i| self.index[i].1),
+        }
+    }
+
+    /// Check if a character is in the code page
+    pub fn contains(&self, c: char) -> bool {
+        self.byte(c).is_some()
+    }
+
+    /// Get the number of characters
+    pub fn len(&self) -> usize {
+        self.chars().count()
+    }
+
+    /// Check if the code page has no character
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the characters with their byte
+    pub fn chars(&self) -> impl Iterator<Item = (char, u8)> + '_ {
+        self.table
+            .iter()
+            .enumerate()
+            .filter(|(_, &c)| c != '\0')
+            .map(|(byte, &c)| (c, byte as u8))
+    }
+}
+
+impl From<PageCode> for CodePage {
+    fn from(value: PageCode) -> Self {
+        page_codes::code_page(value).clone()
+    }
+}
+
+/// Characters of a table with their byte, sorted by character (the bytes without character first)
+pub(crate) const fn index(table: &[char; 256]) -> [(char, u8); 256] {
+    let mut index = [('\0', 0); 256];
+    let mut i = 0;
+    while i < index.len() {
+        let entry = (table[i], i as u8);
+        let mut j = i;
+        while j > 0 && index[j - 1].0 as u32 > entry.0 as u32 {
+            index[j] = index[j - 1];
+            j -= 1;
+        }
+        index[j] = entry;
+        i += 1;
+    }
+    index
+}
+
+/// Check if a character can be mapped to a byte: only control characters are mapped to control bytes
+fn allowed(byte: u8, c: char) -> bool {
+    !(byte < 0x20 || byte == 0x7F) || c == '\0' || c.is_control()
+}
+
+/// Hexadecimal number without its `0x` prefix
+fn hexadecimal(value: &str) -> &str {
+    value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let mut chars = ['\0'; 256];
+        chars[0x80] = 'Ж';
+        chars[0x24] = '¤';
+        let code_page = CodePage::new(PageCode::PC866, 59, chars);
+
+        assert_eq!(code_page.len(), 2);
+        assert_eq!(code_page.byte('Ж'), Some(0x80));
+        assert_eq!(code_page.char(0x24), Some('¤'));
+        assert_eq!(code_page.char(0x41), None);
+
+        // Printable characters are not mapped to control bytes
+        chars[0x1B] = '←';
+        chars[0x7F] = '⌂';
+        chars[0x0A] = '\n';
+        let code_page = CodePage::new(PageCode::PC866, 59, chars);
+        assert_eq!(code_page.byte('←'), None);
+        assert_eq!(code_page.byte('⌂'), None);
+        assert_eq!(code_page.byte('\n'), Some(0x0A));
+    }
+
+    #[test]
+    fn test_from_mapping() {
+        let mapping = "# PC866 clone\n0x80\t0x0410\t#CYRILLIC CAPITAL LETTER A\n0x81\t\t#UNDEFINED\n\n0xF0 0x401\n";
+        let code_page = CodePage::from_mapping(PageCode::PC866, 17, mapping).unwrap();
+
+        assert_eq!(code_page.len(), 2);
+        assert_eq!(code_page.char(0x80), Some('А'));
+        assert_eq!(code_page.byte('Ё'), Some(0xF0));
+        assert_eq!(code_page.char(0x81), None);
+
+        assert_eq!(
+            CodePage::from_mapping(PageCode::PC866, 17, "0x80 0x0410\n0x100 0x0411")
+                .unwrap_err()
+                .to_string(),
+            "Input error: invalid code page mapping at line 2: \"0x100 0x0411\""
+        );
+        assert!(CodePage::from_mapping(PageCode::PC866, 17, "0x80 0xD800").is_err());
+        assert_eq!(
+            CodePage::from_mapping(PageCode::PC437, 0, "0x1B 0x001B\n0x1B 0x2190")
+                .unwrap_err()
+                .to_string(),
+            "Input error: invalid code page mapping at line 2: '←' mapped to the control byte 0x1B"
+        );
+        assert!(CodePage::from_file(PageCode::PC866, 17, "missing.txt").is_err());
+    }
+
+    #[test]
+    fn test_with_char() {
+        let code_page = CodePage::from(PageCode::WPC1252);
+        assert_eq!(code_page.number(), 16);
+        assert_eq!(code_page.byte('€'), Some(0x80));
+
+        let code_page = code_page.with_number(71).with_char(0x80, 'Ł').with_char(0xA4, '\0');
+        assert_eq!(code_page.number(), 71);
+        assert_eq!(code_page.byte('€'), None);
+        assert_eq!(code_page.byte('Ł'), Some(0x80));
+        assert_eq!(code_page.char(0xA4), None);
+        assert!(!code_page.contains('¤'));
+
+        let code_page = code_page.with_char(0x1B, '←');
+        assert_eq!(code_page.char(0x1B), None);
+    }
+}
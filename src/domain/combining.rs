@@ -0,0 +1,100 @@
+//! Composition and decomposition of the combining marks for the page code tables
+
+use unicode_normalization::{
+    char::{canonical_combining_class, compose, is_combining_mark},
+    UnicodeNormalization,
+};
+
+/// Compose the characters followed by combining marks (NFC)
+///
+/// Each composed character comes with the index of its first character in `text`, the sequences of characters
+/// composing together being normalized separately.
+pub(crate) fn compose_text(text: &str) -> Vec<(usize, char)> {
+    let mut composed = vec![];
+    let mut sequence = (0, String::new());
+
+    for (i, c) in text.chars().enumerate() {
+        let joins =
+            canonical_combining_class(c) != 0 || sequence.1.nfc().last().is_some_and(|last| compose(last, c).is_some());
+        if !joins && !sequence.1.is_empty() {
+            compose_sequence(&std::mem::take(&mut sequence), &mut composed);
+        }
+        if sequence.1.is_empty() {
+            sequence.0 = i;
+        }
+        sequence.1.push(c);
+    }
+    compose_sequence(&sequence, &mut composed);
+
+    composed
+}
+
+/// Compose a sequence of characters starting at `start`, keeping their indices if none of them was composed
+fn compose_sequence((start, sequence): &(usize, String), composed: &mut Vec<(usize, char)>) {
+    let chars = sequence.nfc().collect::<Vec<_>>();
+    match chars.len() == sequence.chars().count() {
+        true => composed.extend(chars.into_iter().enumerate().map(|(i, c)| (start + i, c))),
+        false => composed.extend(chars.into_iter().map(|c| (*start, c))),
+    }
+}
+
+/// Decompose a character missing from a page code table into characters of the table
+///
+/// The character is decomposed (NFD), then its combining marks are composed back with the base character
+/// as long as the composition is in the table: `ệ` becomes `ê` + U+0323 in WPC1258.
+/// Returns `None` when the base character or one of the remaining combining marks is not in the table.
+pub(crate) fn decompose(c: char, contains: impl Fn(char) -> bool) -> Option<Vec<char>> {
+    let mut chars = c.nfd();
+    let mut base = chars.next()?;
+    let mut marks = chars.collect::<Vec<_>>();
+    if marks.is_empty() {
+        return None;
+    }
+
+    while let Some((i, composed)) = marks
+        .iter()
+        .enumerate()
+        .find_map(|(i, &mark)| compose(base, mark).filter(|&c| contains(c)).map(|c| (i, c)))
+    {
+        base = composed;
+        marks.remove(i);
+    }
+
+    let contained = |c: char| c.is_ascii() || contains(c);
+    match contained(base) && marks.iter().all(|&mark| is_combining_mark(mark) && contained(mark)) {
+        true => Some([vec![base], marks].concat()),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{page_codes, PageCode};
+
+    fn contains(page_code: PageCode) -> impl Fn(char) -> bool {
+        let table = page_codes::code_page(page_code);
+        move |c| table.contains(c)
+    }
+
+    #[test]
+    fn test_compose_text() {
+        assert_eq!(compose_text("e\u{0301}"), vec![(0, 'é')]);
+        assert_eq!(compose_text("ก\u{0E48}"), vec![(0, 'ก'), (1, '\u{0E48}')]);
+        assert_eq!(
+            compose_text("e\u{0301}xa\u{0323}\u{0302}"),
+            vec![(0, 'é'), (2, 'x'), (3, 'ậ')]
+        );
+        assert_eq!(compose_text("\u{1100}\u{1161}\u{11A8}"), vec![(0, '각')]);
+    }
+
+    #[test]
+    fn test_decompose() {
+        let wpc1258 = contains(PageCode::WPC1258);
+        assert_eq!(decompose('ệ', &wpc1258), Some(vec!['ê', '\u{0323}']));
+        assert_eq!(decompose('ở', &wpc1258), Some(vec!['ơ', '\u{0309}']));
+        assert_eq!(decompose('ả', &wpc1258), Some(vec!['a', '\u{0309}']));
+        assert_eq!(decompose('ş', &wpc1258), None);
+        assert_eq!(decompose('ệ', contains(PageCode::PC437)), None);
+    }
+}
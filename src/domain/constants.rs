@@ -3,11 +3,14 @@
 pub const _EOL: &str = "\n";
 pub const NUL: u8 = 0x00; // Null
 pub const EOT: u8 = 0x04; // End of transmission
+pub const HT: u8 = 0x09; // Horizontal tab
 pub const LF: u8 = 0x0A; // Line feed
+pub const FF: u8 = 0x0C; // Form feed
 pub const _VT: u8 = 0x0B; // Vertical tab
 pub const _CR: u8 = 0x0D; // Carriage return
 pub const DLE: u8 = 0x10; // Data link escape
 pub const ESC: u8 = 0x1B;
+pub const FS: u8 = 0x1C; // File separator
 pub const GS: u8 = 0x1D; // Group separator
 pub const CAN: u8 = 0x18; // Cancel
 
@@ -65,6 +68,45 @@ pub const GS_TEXT_SIZE_SELECT: &[u8] = &[GS, b'!'];
 pub const ESC_TEXT_UPSIDE_DOWN_OFF: &[u8] = &[ESC, b'{', 0];
 pub const ESC_TEXT_UPSIDE_DOWN_ON: &[u8] = &[ESC, b'{', 1];
 
+pub const ESC_TEXT_PRINT_MODE: &[u8] = &[ESC, b'!'];
+pub const ESC_TEXT_CHARACTER_SPACING: &[u8] = &[ESC, b' '];
+
+pub const ESC_UNIDIRECTIONAL_OFF: &[u8] = &[ESC, b'U', 0];
+pub const ESC_UNIDIRECTIONAL_ON: &[u8] = &[ESC, b'U', 1];
+
+// Character effects
+pub const GS_TEXT_CHARACTER_COLOUR: &[u8] = &[GS, b'(', b'N', 2, 0, 48];
+pub const GS_TEXT_BACKGROUND_COLOUR: &[u8] = &[GS, b'(', b'N', 2, 0, 49];
+pub const GS_TEXT_SHADING: &[u8] = &[GS, b'(', b'N', 3, 0, 50];
+
+pub const ESC_ABSOLUTE_POSITION: &[u8] = &[ESC, b'$'];
+pub const ESC_RELATIVE_POSITION: &[u8] = &[ESC, b'\\'];
+pub const ESC_TAB_POSITIONS: &[u8] = &[ESC, b'D'];
+pub const GS_LINE_START: &[u8] = &[GS, b'T'];
+pub const GS_LEFT_MARGIN: &[u8] = &[GS, b'L'];
+pub const GS_PRINT_AREA_WIDTH: &[u8] = &[GS, b'W'];
+
+// Page mode
+pub const ESC_PAGE_MODE: &[u8] = &[ESC, b'L'];
+pub const ESC_STANDARD_MODE: &[u8] = &[ESC, b'S'];
+pub const ESC_PAGE_AREA: &[u8] = &[ESC, b'W'];
+pub const ESC_PAGE_DIRECTION: &[u8] = &[ESC, b'T'];
+pub const GS_PAGE_VERTICAL_POSITION: &[u8] = &[GS, b'$'];
+pub const GS_PAGE_RELATIVE_VERTICAL_POSITION: &[u8] = &[GS, b'\\'];
+pub const FF_PAGE_PRINT_EXIT: &[u8] = &[FF]; // Print and return to standard mode
+pub const ESC_PAGE_PRINT: &[u8] = &[ESC, FF]; // Print and stay in page mode
+
+// Kanji
+pub const FS_KANJI_MODE_ON: &[u8] = &[FS, b'&'];
+pub const FS_KANJI_MODE_OFF: &[u8] = &[FS, b'.'];
+pub const FS_KANJI_CODE_SYSTEM: &[u8] = &[FS, b'C'];
+pub const FS_KANJI_PRINT_MODE: &[u8] = &[FS, b'!'];
+
+// Unicode
+pub const FS_CHARACTER_ENCODING_ONE_BYTE: &[u8] = &[FS, b'(', b'C', 2, 0, 48, 1];
+pub const FS_CHARACTER_ENCODING_UTF8: &[u8] = &[FS, b'(', b'C', 2, 0, 48, 2];
+pub const FS_FONT_PRIORITY: &[u8] = &[FS, b'(', b'C', 3, 0, 60];
+
 // Printer Status
 pub const DLE_REAL_TIME_STATUS: &[u8] = &[DLE, EOT];
 
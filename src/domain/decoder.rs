@@ -0,0 +1,948 @@
+//! Decoder used to turn an ESC/POS byte stream back into commands
+
+use super::{constants::*, page_codes, KanjiEncoding, PageCode};
+
+/// Decoded command
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodedCommand {
+    /// Text run decoded through the active page code
+    Text(String),
+    /// LF
+    LineFeed,
+    /// HT
+    HorizontalTab,
+    /// CR
+    CarriageReturn,
+    /// CAN
+    Cancel,
+    /// FF (print the data of page mode and return to standard mode)
+    FormFeed,
+    /// ESC @
+    Init,
+    /// ESC ? LF NUL
+    Reset,
+    /// ESC t n
+    PageCode(u8),
+    /// ESC R n
+    CharacterSet(u8),
+    /// ESC E n
+    Bold(bool),
+    /// ESC - n
+    Underline(u8),
+    /// ESC G n
+    DoubleStrike(bool),
+    /// ESC M n
+    Font(u8),
+    /// ESC V n
+    Flip(bool),
+    /// ESC a n
+    Justify(u8),
+    /// ESC { n
+    UpsideDown(bool),
+    /// ESC ! n
+    PrintMode(u8),
+    /// ESC SP n
+    CharacterSpacing(u8),
+    /// ESC U n
+    Unidirectional(bool),
+    /// ESC $ nL nH
+    AbsolutePosition(u16),
+    /// ESC \\ nL nH
+    RelativePosition(i16),
+    /// ESC D n1...nk NUL
+    TabPositions(Vec<u8>),
+    /// GS T n
+    LineStart(u8),
+    /// GS L nL nH
+    LeftMargin(u16),
+    /// GS W nL nH
+    PrintAreaWidth(u16),
+    /// ESC L
+    PageMode,
+    /// ESC S
+    StandardMode,
+    /// ESC W xL xH yL yH dxL dxH dyL dyH
+    PageArea { x: u16, y: u16, width: u16, height: u16 },
+    /// ESC T n
+    PageDirection(u8),
+    /// ESC FF
+    PrintPage,
+    /// GS $ nL nH
+    VerticalPosition(u16),
+    /// GS \\ nL nH
+    RelativeVerticalPosition(i16),
+    /// ESC 2
+    ResetLineSpacing,
+    /// ESC 3 n
+    LineSpacing(u8),
+    /// ESC d n
+    Feed(u8),
+    /// ESC p m
+    CashDrawer(u8),
+    /// GS ! n
+    TextSize { width: u8, height: u8 },
+    /// GS B n
+    Reverse(bool),
+    /// GS b n
+    Smoothing(bool),
+    /// GS V m \[n\]
+    Cut { partial: bool, feed: Option<u8> },
+    /// GS P x y
+    MotionUnits { x: u8, y: u8 },
+    /// DLE EOT n \[a\]
+    RealTimeStatus { n: u8, a: Option<u8> },
+    /// GS H n
+    BarcodePosition(u8),
+    /// GS f n
+    BarcodeFont(u8),
+    /// GS h n
+    BarcodeHeight(u8),
+    /// GS w n
+    BarcodeWidth(u8),
+    /// GS k m d1...dk
+    Barcode { system: u8, data: Vec<u8> },
+    /// GS ( k pL pH cn fn \[parameters\]
+    Code2D { cn: u8, function: u8, parameters: Vec<u8> },
+    /// GS ( N pL pH fn \[parameters\]
+    CharacterEffects { function: u8, parameters: Vec<u8> },
+    /// GS v 0 m xL xH yL yH d1...dk
+    BitImage {
+        mode: u8,
+        width_bytes: u16,
+        height: u16,
+        data: Vec<u8>,
+    },
+    /// GS ( L pL pH m fn \[parameters\] or GS 8 L p1 p2 p3 p4 m fn \[parameters\]
+    Graphics { m: u8, function: u8, parameters: Vec<u8> },
+    /// FS & or FS .
+    KanjiMode(bool),
+    /// FS C n
+    KanjiCodeSystem(u8),
+    /// FS ! n
+    KanjiPrintMode(u8),
+    /// FS ( C pL pH fn m (fn = 48)
+    UnicodeMode(bool),
+    /// FS ( C pL pH fn m a (fn = 60)
+    FontPriority { priority: u8, font: u8 },
+    /// Sequence not recognized by the decoder
+    Unknown(Vec<u8>),
+    /// Sequence cut before its end
+    Truncated(Vec<u8>),
+}
+
+impl DecodedCommand {
+    /// Check if the command is an unknown or truncated sequence
+    pub fn is_error(&self) -> bool {
+        matches!(self, DecodedCommand::Unknown(_) | DecodedCommand::Truncated(_))
+    }
+}
+
+/// Decoded instruction with its position in the byte stream
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedInstruction {
+    /// Offset of the first byte in the stream
+    pub offset: usize,
+    /// Raw bytes of the instruction
+    pub bytes: Vec<u8>,
+    /// Decoded command
+    pub command: DecodedCommand,
+}
+
+/// Decoder
+///
+/// Parses the bytes produced by [`Protocol`](super::Protocol) (or captured from a printer connection)
+/// into a list of [`DecodedInstruction`].
+/// Unknown or truncated sequences are reported with their offset instead of failing.
+///
+/// # Examples
+///
+/// ```rust
+/// use escpos::utils::*;
+///
+/// let decoder = Decoder::new(Some(PageCode::PC858));
+/// let instructions = decoder.decode(&[27, 64, 27, 69, 1, b'H', b'i', 0xD5, 10]);
+///
+/// assert_eq!(instructions[0].command, DecodedCommand::Init);
+/// assert_eq!(instructions[1].command, DecodedCommand::Bold(true));
+/// assert_eq!(instructions[2].command, DecodedCommand::Text("Hi€".to_string()));
+/// assert_eq!(instructions[3].command, DecodedCommand::LineFeed);
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Decoder {
+    page_code: Option<PageCode>,
+    kanji_encoding: Option<KanjiEncoding>,
+}
+
+impl Decoder {
+    /// Create a new `Decoder` with the page code active at the beginning of the stream
+    pub fn new(page_code: Option<PageCode>) -> Self {
+        Self {
+            page_code,
+            kanji_encoding: None,
+        }
+    }
+
+    /// Set the double-byte encoding of the text printed in Kanji mode (after FS &)
+    pub fn with_kanji_encoding(mut self, encoding: KanjiEncoding) -> Self {
+        self.kanji_encoding = Some(encoding);
+        self
+    }
+
+    /// Decode a byte stream
+    pub fn decode(&self, data: &[u8]) -> Vec<DecodedInstruction> {
+        let mut instructions = vec![];
+        let mut page_code = self.page_code;
+        let mut kanji_encoding = None;
+        let mut unicode = false;
+        let mut offset = 0;
+
+        while offset < data.len() {
+            let (command, length) = match unicode {
+                true => Self::parse(&data[offset..], None, None),
+                false => Self::parse(&data[offset..], page_code, kanji_encoding),
+            };
+
+            match command {
+                DecodedCommand::Init => {
+                    page_code = self.page_code;
+                    kanji_encoding = None;
+                    unicode = false;
+                }
+                DecodedCommand::PageCode(n) => page_code = PageCode::try_from(n).ok(),
+                DecodedCommand::KanjiMode(enabled) => kanji_encoding = self.kanji_encoding.filter(|_| enabled),
+                DecodedCommand::UnicodeMode(enabled) => unicode = enabled,
+                _ => (),
+            }
+
+            instructions.push(DecodedInstruction {
+                offset,
+                bytes: data[offset..offset + length].to_vec(),
+                command,
+            });
+            offset += length;
+        }
+
+        instructions
+    }
+
+    /// Parse the next command and return it with the number of bytes consumed
+    fn parse(
+        data: &[u8],
+        page_code: Option<PageCode>,
+        kanji_encoding: Option<KanjiEncoding>,
+    ) -> (DecodedCommand, usize) {
+        match data[0] {
+            LF => (DecodedCommand::LineFeed, 1),
+            HT => (DecodedCommand::HorizontalTab, 1),
+            _CR => (DecodedCommand::CarriageReturn, 1),
+            CAN => (DecodedCommand::Cancel, 1),
+            FF => (DecodedCommand::FormFeed, 1),
+            ESC => Self::parse_esc(data),
+            GS => Self::parse_gs(data),
+            DLE => Self::parse_dle(data),
+            FS => Self::parse_fs(data),
+            b if b < 0x20 => (DecodedCommand::Unknown(vec![b]), 1),
+            _ => {
+                let length = data.iter().position(|&b| b < 0x20).unwrap_or(data.len());
+                (
+                    DecodedCommand::Text(Self::decode_text(&data[..length], page_code, kanji_encoding)),
+                    length,
+                )
+            }
+        }
+    }
+
+    /// Parse ESC commands
+    fn parse_esc(data: &[u8]) -> (DecodedCommand, usize) {
+        let Some(&n) = data.get(1) else {
+            return Self::truncated(data);
+        };
+
+        match n {
+            b'@' => (DecodedCommand::Init, 2),
+            b'L' => (DecodedCommand::PageMode, 2),
+            b'S' => (DecodedCommand::StandardMode, 2),
+            FF => (DecodedCommand::PrintPage, 2),
+            b'W' => Self::fixed(data, 10, |p| {
+                let value = |i: usize| u16::from_le_bytes([p[i], p[i + 1]]);
+                DecodedCommand::PageArea {
+                    x: value(2),
+                    y: value(4),
+                    width: value(6),
+                    height: value(8),
+                }
+            }),
+            b'T' => Self::fixed(data, 3, |p| DecodedCommand::PageDirection(p[2] % 48)),
+            b'\\' => Self::fixed(data, 4, |p| {
+                DecodedCommand::RelativePosition(i16::from_le_bytes([p[2], p[3]]))
+            }),
+            b'D' => match data[2..].iter().position(|&b| b == NUL) {
+                Some(length) => (DecodedCommand::TabPositions(data[2..2 + length].to_vec()), length + 3),
+                None => Self::truncated(data),
+            },
+            b'2' => (DecodedCommand::ResetLineSpacing, 2),
+            b'?' if data.starts_with(ESC_HARDWARE_RESET) => (DecodedCommand::Reset, ESC_HARDWARE_RESET.len()),
+            b't' => Self::fixed(data, 3, |p| DecodedCommand::PageCode(p[2])),
+            b'R' => Self::fixed(data, 3, |p| DecodedCommand::CharacterSet(p[2])),
+            b'E' => Self::fixed(data, 3, |p| DecodedCommand::Bold(p[2] & 1 == 1)),
+            b'-' => Self::fixed(data, 3, |p| DecodedCommand::Underline(p[2] % 48)),
+            b'G' => Self::fixed(data, 3, |p| DecodedCommand::DoubleStrike(p[2] & 1 == 1)),
+            b'M' => Self::fixed(data, 3, |p| DecodedCommand::Font(p[2] % 48)),
+            b'V' => Self::fixed(data, 3, |p| DecodedCommand::Flip(p[2] & 1 == 1)),
+            b'a' => Self::fixed(data, 3, |p| DecodedCommand::Justify(p[2] % 48)),
+            b'{' => Self::fixed(data, 3, |p| DecodedCommand::UpsideDown(p[2] & 1 == 1)),
+            b'!' => Self::fixed(data, 3, |p| DecodedCommand::PrintMode(p[2])),
+            b' ' => Self::fixed(data, 3, |p| DecodedCommand::CharacterSpacing(p[2])),
+            b'U' => Self::fixed(data, 3, |p| DecodedCommand::Unidirectional(p[2] & 1 == 1)),
+            b'3' => Self::fixed(data, 3, |p| DecodedCommand::LineSpacing(p[2])),
+            b'd' => Self::fixed(data, 3, |p| DecodedCommand::Feed(p[2])),
+            b'p' => Self::fixed(data, 3, |p| DecodedCommand::CashDrawer(p[2] % 48)),
+            b'$' => Self::fixed(data, 4, |p| {
+                DecodedCommand::AbsolutePosition(u16::from_le_bytes([p[2], p[3]]))
+            }),
+            _ => (DecodedCommand::Unknown(data[..2].to_vec()), 2),
+        }
+    }
+
+    /// Parse GS commands
+    fn parse_gs(data: &[u8]) -> (DecodedCommand, usize) {
+        let Some(&n) = data.get(1) else {
+            return Self::truncated(data);
+        };
+
+        match n {
+            b'!' => Self::fixed(data, 3, |p| DecodedCommand::TextSize {
+                width: (p[2] >> 4) + 1,
+                height: (p[2] & 0x0F) + 1,
+            }),
+            b'B' => Self::fixed(data, 3, |p| DecodedCommand::Reverse(p[2] & 1 == 1)),
+            b'b' => Self::fixed(data, 3, |p| DecodedCommand::Smoothing(p[2] & 1 == 1)),
+            b'H' => Self::fixed(data, 3, |p| DecodedCommand::BarcodePosition(p[2] % 48)),
+            b'f' => Self::fixed(data, 3, |p| DecodedCommand::BarcodeFont(p[2] % 48)),
+            b'h' => Self::fixed(data, 3, |p| DecodedCommand::BarcodeHeight(p[2])),
+            b'w' => Self::fixed(data, 3, |p| DecodedCommand::BarcodeWidth(p[2])),
+            b'P' => Self::fixed(data, 4, |p| DecodedCommand::MotionUnits { x: p[2], y: p[3] }),
+            b'T' => Self::fixed(data, 3, |p| DecodedCommand::LineStart(p[2] % 48)),
+            b'L' => Self::fixed(data, 4, |p| {
+                DecodedCommand::LeftMargin(u16::from_le_bytes([p[2], p[3]]))
+            }),
+            b'W' => Self::fixed(data, 4, |p| {
+                DecodedCommand::PrintAreaWidth(u16::from_le_bytes([p[2], p[3]]))
+            }),
+            b'$' => Self::fixed(data, 4, |p| {
+                DecodedCommand::VerticalPosition(u16::from_le_bytes([p[2], p[3]]))
+            }),
+            b'\\' => Self::fixed(data, 4, |p| {
+                DecodedCommand::RelativeVerticalPosition(i16::from_le_bytes([p[2], p[3]]))
+            }),
+            b'V' => Self::parse_cut(data),
+            b'k' => Self::parse_barcode(data),
+            b'(' => Self::parse_gs_parenthesis(data),
+            b'8' => Self::parse_graphics_large(data),
+            b'v' => Self::parse_bit_image(data),
+            _ => (DecodedCommand::Unknown(data[..2].to_vec()), 2),
+        }
+    }
+
+    /// Parse DLE commands
+    fn parse_dle(data: &[u8]) -> (DecodedCommand, usize) {
+        match data {
+            [DLE] | [DLE, EOT] => Self::truncated(data),
+            [DLE, EOT, n @ (7 | 8 | 18), ..] => {
+                Self::fixed(data, 4, |p| DecodedCommand::RealTimeStatus { n: *n, a: Some(p[3]) })
+            }
+            [DLE, EOT, n, NUL, ..] => (DecodedCommand::RealTimeStatus { n: *n, a: Some(NUL) }, 4),
+            [DLE, EOT, n, ..] => (DecodedCommand::RealTimeStatus { n: *n, a: None }, 3),
+            _ => (DecodedCommand::Unknown(data[..2].to_vec()), 2),
+        }
+    }
+
+    /// Parse FS commands
+    fn parse_fs(data: &[u8]) -> (DecodedCommand, usize) {
+        let Some(&n) = data.get(1) else {
+            return Self::truncated(data);
+        };
+
+        match n {
+            b'&' => (DecodedCommand::KanjiMode(true), 2),
+            b'.' => (DecodedCommand::KanjiMode(false), 2),
+            b'C' => Self::fixed(data, 3, |p| DecodedCommand::KanjiCodeSystem(p[2] % 48)),
+            b'!' => Self::fixed(data, 3, |p| DecodedCommand::KanjiPrintMode(p[2])),
+            b'(' => Self::parse_fs_parenthesis(data),
+            _ => (DecodedCommand::Unknown(data[..2].to_vec()), 2),
+        }
+    }
+
+    /// Parse FS ( C pL pH fn \[parameters\]
+    fn parse_fs_parenthesis(data: &[u8]) -> (DecodedCommand, usize) {
+        if data.len() < 5 {
+            return Self::truncated(data);
+        }
+
+        let length = 5 + data[3] as usize + ((data[4] as usize) << 8);
+        if data.len() < length {
+            return Self::truncated(data);
+        }
+
+        match (data[2], &data[5..length]) {
+            (b'C', [48, m]) => (DecodedCommand::UnicodeMode(*m % 48 == 2), length),
+            (b'C', [60, priority, font]) => (
+                DecodedCommand::FontPriority {
+                    priority: *priority,
+                    font: *font,
+                },
+                length,
+            ),
+            _ => (DecodedCommand::Unknown(data[..length].to_vec()), length),
+        }
+    }
+
+    /// Parse GS V m \[n\]
+    fn parse_cut(data: &[u8]) -> (DecodedCommand, usize) {
+        let Some(&m) = data.get(2) else {
+            return Self::truncated(data);
+        };
+
+        match m {
+            0 | 48 => (
+                DecodedCommand::Cut {
+                    partial: false,
+                    feed: None,
+                },
+                3,
+            ),
+            1 | 49 => (
+                DecodedCommand::Cut {
+                    partial: true,
+                    feed: None,
+                },
+                3,
+            ),
+            65 | 97 | 103 => Self::fixed(data, 4, |p| DecodedCommand::Cut {
+                partial: false,
+                feed: Some(p[3]),
+            }),
+            66 | 98 | 104 => Self::fixed(data, 4, |p| DecodedCommand::Cut {
+                partial: true,
+                feed: Some(p[3]),
+            }),
+            _ => (DecodedCommand::Unknown(data[..3].to_vec()), 3),
+        }
+    }
+
+    /// Parse GS k m d1...dk NUL or GS k m n d1...dn
+    fn parse_barcode(data: &[u8]) -> (DecodedCommand, usize) {
+        let Some(&system) = data.get(2) else {
+            return Self::truncated(data);
+        };
+
+        match system {
+            0..=6 => match data[3..].iter().position(|&b| b == NUL) {
+                Some(end) => (
+                    DecodedCommand::Barcode {
+                        system,
+                        data: data[3..3 + end].to_vec(),
+                    },
+                    end + 4,
+                ),
+                None => Self::truncated(data),
+            },
+            65..=79 => {
+                let Some(&n) = data.get(3) else {
+                    return Self::truncated(data);
+                };
+                Self::fixed(data, 4 + n as usize, |p| DecodedCommand::Barcode {
+                    system,
+                    data: p[4..].to_vec(),
+                })
+            }
+            _ => (DecodedCommand::Unknown(data[..3].to_vec()), 3),
+        }
+    }
+
+    /// Parse GS ( k, GS ( L and GS ( N commands
+    fn parse_gs_parenthesis(data: &[u8]) -> (DecodedCommand, usize) {
+        if data.len() < 5 {
+            return Self::truncated(data);
+        }
+
+        let length = 5 + data[3] as usize + ((data[4] as usize) << 8);
+        if data.len() < length {
+            return Self::truncated(data);
+        }
+
+        let payload = &data[5..length];
+        match (data[2], payload) {
+            (b'k', [cn, function, parameters @ ..]) => (
+                DecodedCommand::Code2D {
+                    cn: *cn,
+                    function: *function,
+                    parameters: parameters.to_vec(),
+                },
+                length,
+            ),
+            (b'L', [m, function, parameters @ ..]) => (
+                DecodedCommand::Graphics {
+                    m: *m,
+                    function: *function,
+                    parameters: parameters.to_vec(),
+                },
+                length,
+            ),
+            (b'N', [function, parameters @ ..]) => (
+                DecodedCommand::CharacterEffects {
+                    function: *function,
+                    parameters: parameters.to_vec(),
+                },
+                length,
+            ),
+            _ => (DecodedCommand::Unknown(data[..length].to_vec()), length),
+        }
+    }
+
+    /// Parse GS 8 L p1 p2 p3 p4 m fn \[parameters\]
+    fn parse_graphics_large(data: &[u8]) -> (DecodedCommand, usize) {
+        match data {
+            [GS, b'8', b'L', p1, p2, p3, p4, ..] => {
+                // The length may not fit in a `usize` on 32-bit targets
+                let length = 7 + u64::from(u32::from_le_bytes([*p1, *p2, *p3, *p4]));
+                let length = match usize::try_from(length) {
+                    Ok(length) if length <= data.len() => length,
+                    _ => return Self::truncated(data),
+                };
+
+                match &data[7..length] {
+                    [m, function, parameters @ ..] => (
+                        DecodedCommand::Graphics {
+                            m: *m,
+                            function: *function,
+                            parameters: parameters.to_vec(),
+                        },
+                        length,
+                    ),
+                    _ => (DecodedCommand::Unknown(data[..length].to_vec()), length),
+                }
+            }
+            [GS, b'8'] | [GS, b'8', b'L', ..] => Self::truncated(data),
+            _ => (DecodedCommand::Unknown(data[..2].to_vec()), 2),
+        }
+    }
+
+    /// Parse GS v 0 m xL xH yL yH d1...dk
+    fn parse_bit_image(data: &[u8]) -> (DecodedCommand, usize) {
+        match data {
+            [GS, b'v', b'0', mode, xl, xh, yl, yh, ..] => {
+                let width_bytes = u16::from_le_bytes([*xl, *xh]);
+                let height = u16::from_le_bytes([*yl, *yh]);
+                let length = 8 + width_bytes as usize * height as usize;
+
+                Self::fixed(data, length, |p| DecodedCommand::BitImage {
+                    mode: *mode % 48,
+                    width_bytes,
+                    height,
+                    data: p[8..].to_vec(),
+                })
+            }
+            [GS, b'v'] | [GS, b'v', b'0', ..] => Self::truncated(data),
+            _ => (DecodedCommand::Unknown(data[..2].to_vec()), 2),
+        }
+    }
+
+    /// Build a command of fixed length or report it as truncated
+    fn fixed<F>(data: &[u8], length: usize, f: F) -> (DecodedCommand, usize)
+    where
+        F: FnOnce(&[u8]) -> DecodedCommand,
+    {
+        if data.len() < length {
+            return Self::truncated(data);
+        }
+        (f(&data[..length]), length)
+    }
+
+    /// Report the remaining bytes as a truncated sequence
+    fn truncated(data: &[u8]) -> (DecodedCommand, usize) {
+        (DecodedCommand::Truncated(data.to_vec()), data.len())
+    }
+
+    /// Decode a text run with the page code, or the double-byte encoding in Kanji mode
+    fn decode_text(data: &[u8], page_code: Option<PageCode>, kanji_encoding: Option<KanjiEncoding>) -> String {
+        if let Some(encoding) = kanji_encoding {
+            return encoding.codec().decode_without_bom_handling(data).0.into_owned();
+        }
+
+        let table = page_code.map(page_codes::code_page);
+
+        match table {
+            Some(table) => data
+                .iter()
+                .map(|&b| match b {
+                    0..=0x7F => b as char,
+                    _ => table.char(b).unwrap_or(char::REPLACEMENT_CHARACTER),
+                })
+                .collect(),
+            None => String::from_utf8_lossy(data).into_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{domain::*, io::encoder::Encoder};
+
+    fn commands(decoder: Decoder, data: &[u8]) -> Vec<DecodedCommand> {
+        decoder.decode(data).into_iter().map(|i| i.command).collect()
+    }
+
+    #[test]
+    fn test_decode_protocol_commands() {
+        let protocol = Protocol::new(Encoder::default());
+        let data = [
+            protocol.init(),
+            protocol.bold(true),
+            protocol.underline(UnderlineMode::Double),
+            protocol.double_strike(true),
+            protocol.font(Font::B),
+            protocol.flip(false),
+            protocol.justify(JustifyMode::CENTER),
+            protocol.reverse_colours(true),
+            protocol.smoothing(true),
+            protocol.text_size(2, 3).unwrap(),
+            protocol.line_spacing(40),
+            protocol.reset_line_spacing(),
+            protocol.upside_down(true),
+            protocol
+                .print_mode(PrintMode::new().with_bold(true).with_double_height(true))
+                .unwrap(),
+            protocol.character_spacing(3),
+            protocol.unidirectional(true),
+            protocol.shading(true, TextColour::Colour1),
+            protocol.absolute_position(300),
+            protocol.relative_position(-24),
+            protocol.tab_positions(&[8, 16]).unwrap(),
+            protocol.line_start(true),
+            protocol.left_margin(300),
+            protocol.print_area_width(432).unwrap(),
+            protocol.page_mode(),
+            protocol.page_area(0, 10, 512, 300).unwrap(),
+            protocol.page_direction(PageDirection::BottomToTop),
+            protocol.vertical_position(20),
+            protocol.relative_vertical_position(-2),
+            protocol.print_page(false),
+            protocol.print_page(true),
+            protocol.standard_mode(),
+            protocol.feed(2),
+            protocol.cash_drawer(CashDrawer::Pin5),
+            protocol.character_set(CharacterSet::France),
+            protocol.motion_units(4, 8),
+            protocol.real_time_status(RealTimeStatusRequest::Printer),
+            protocol.real_time_status(RealTimeStatusRequest::Peeler),
+            protocol.cut(false),
+            vec![DLE, EOT, 2],
+            protocol.reset(),
+            protocol.cancel(),
+        ]
+        .concat();
+
+        assert_eq!(
+            commands(Decoder::default(), &data),
+            vec![
+                DecodedCommand::Init,
+                DecodedCommand::Bold(true),
+                DecodedCommand::Underline(2),
+                DecodedCommand::DoubleStrike(true),
+                DecodedCommand::Font(1),
+                DecodedCommand::Flip(false),
+                DecodedCommand::Justify(1),
+                DecodedCommand::Reverse(true),
+                DecodedCommand::Smoothing(true),
+                DecodedCommand::TextSize { width: 2, height: 3 },
+                DecodedCommand::LineSpacing(40),
+                DecodedCommand::ResetLineSpacing,
+                DecodedCommand::UpsideDown(true),
+                DecodedCommand::PrintMode(24),
+                DecodedCommand::CharacterSpacing(3),
+                DecodedCommand::Unidirectional(true),
+                DecodedCommand::CharacterEffects {
+                    function: 50,
+                    parameters: vec![49, 49],
+                },
+                DecodedCommand::AbsolutePosition(300),
+                DecodedCommand::RelativePosition(-24),
+                DecodedCommand::TabPositions(vec![8, 16]),
+                DecodedCommand::LineStart(1),
+                DecodedCommand::LeftMargin(300),
+                DecodedCommand::PrintAreaWidth(432),
+                DecodedCommand::PageMode,
+                DecodedCommand::PageArea {
+                    x: 0,
+                    y: 10,
+                    width: 512,
+                    height: 300
+                },
+                DecodedCommand::PageDirection(1),
+                DecodedCommand::VerticalPosition(20),
+                DecodedCommand::RelativeVerticalPosition(-2),
+                DecodedCommand::PrintPage,
+                DecodedCommand::FormFeed,
+                DecodedCommand::StandardMode,
+                DecodedCommand::Feed(2),
+                DecodedCommand::CashDrawer(1),
+                DecodedCommand::CharacterSet(1),
+                DecodedCommand::MotionUnits { x: 4, y: 8 },
+                DecodedCommand::RealTimeStatus { n: 1, a: Some(0) },
+                DecodedCommand::RealTimeStatus { n: 8, a: Some(3) },
+                DecodedCommand::Cut {
+                    partial: false,
+                    feed: Some(0)
+                },
+                DecodedCommand::RealTimeStatus { n: 2, a: None },
+                DecodedCommand::Reset,
+                DecodedCommand::Cancel,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_text_with_page_code() {
+        let protocol = Protocol::new(Encoder::default());
+        let data = [
+            protocol.text("é €", Some(PageCode::PC858)).unwrap(),
+            vec![LF],
+            protocol.page_code(PageCode::WPC1252),
+            protocol.text("Ç ÿ", Some(PageCode::WPC1252)).unwrap(),
+            vec![HT],
+            protocol.init(),
+            vec![0xD5],
+        ]
+        .concat();
+
+        assert_eq!(
+            commands(Decoder::new(Some(PageCode::PC858)), &data),
+            vec![
+                DecodedCommand::Text("é €".to_string()),
+                DecodedCommand::LineFeed,
+                DecodedCommand::PageCode(16),
+                DecodedCommand::Text("Ç ÿ".to_string()),
+                DecodedCommand::HorizontalTab,
+                DecodedCommand::Init,
+                DecodedCommand::Text("€".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_text_without_page_code() {
+        assert_eq!(
+            commands(Decoder::default(), "My text é".as_bytes()),
+            vec![DecodedCommand::Text("My text é".to_string())]
+        );
+    }
+
+    #[cfg(feature = "barcodes")]
+    #[test]
+    fn test_decode_barcode() {
+        let protocol = Protocol::new(Encoder::default());
+        let option = BarcodeOption::default();
+        let data = protocol
+            .barcode("1234567890265", BarcodeSystem::EAN13, option)
+            .unwrap()
+            .concat();
+        let decoded = commands(Decoder::default(), &data);
+
+        assert_eq!(decoded.len(), 5);
+        assert_eq!(
+            decoded[4],
+            DecodedCommand::Barcode {
+                system: 2,
+                data: b"1234567890265".to_vec()
+            }
+        );
+        assert_eq!(
+            commands(Decoder::default(), &[GS, b'k', 73, 3, b'A', b'B', b'C']),
+            vec![DecodedCommand::Barcode {
+                system: 73,
+                data: b"ABC".to_vec()
+            }]
+        );
+    }
+
+    #[cfg(feature = "codes_2d")]
+    #[test]
+    fn test_decode_code_2d() {
+        let protocol = Protocol::new(Encoder::default());
+        let data = protocol.qrcode("test", QRCodeOption::default()).unwrap().concat();
+        let decoded = commands(Decoder::default(), &data);
+
+        assert_eq!(decoded.len(), 5);
+        assert_eq!(
+            decoded[3],
+            DecodedCommand::Code2D {
+                cn: 49,
+                function: 80,
+                parameters: b"0test".to_vec()
+            }
+        );
+        assert_eq!(
+            decoded[4],
+            DecodedCommand::Code2D {
+                cn: 49,
+                function: 81,
+                parameters: vec![48]
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_bit_image() {
+        let data = [GS, b'v', b'0', 0, 2, 0, 1, 0, 0xFF, 0x0F, LF];
+
+        assert_eq!(
+            commands(Decoder::default(), &data),
+            vec![
+                DecodedCommand::BitImage {
+                    mode: 0,
+                    width_bytes: 2,
+                    height: 1,
+                    data: vec![0xFF, 0x0F]
+                },
+                DecodedCommand::LineFeed,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_kanji() {
+        let protocol = Protocol::new(Encoder::default());
+        let data = [
+            protocol.kanji_code_system(KanjiCodeSystem::ShiftJis),
+            protocol.kanji_mode(true),
+            protocol.kanji_print_mode(true, false, true),
+            protocol.kanji_text("ラーメン 800円", KanjiEncoding::ShiftJis).unwrap(),
+            protocol.kanji_mode(false),
+        ]
+        .concat();
+
+        assert_eq!(
+            commands(Decoder::default().with_kanji_encoding(KanjiEncoding::ShiftJis), &data),
+            vec![
+                DecodedCommand::KanjiCodeSystem(1),
+                DecodedCommand::KanjiMode(true),
+                DecodedCommand::KanjiPrintMode(0x84),
+                DecodedCommand::Text("ラーメン 800円".to_string()),
+                DecodedCommand::KanjiMode(false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_unicode() {
+        let protocol = Protocol::new(Encoder::default());
+        let data = [
+            vec![protocol.unicode_mode(true)],
+            protocol.font_priority(&[UnicodeFont::Japanese]).unwrap(),
+            vec![
+                protocol.unicode_text("€ 日本"),
+                protocol.unicode_mode(false),
+                vec![0xD5],
+            ],
+        ]
+        .concat()
+        .concat();
+
+        assert_eq!(
+            commands(Decoder::new(Some(PageCode::PC858)), &data),
+            vec![
+                DecodedCommand::UnicodeMode(true),
+                DecodedCommand::FontPriority { priority: 0, font: 11 },
+                DecodedCommand::Text("€ 日本".to_string()),
+                DecodedCommand::UnicodeMode(false),
+                DecodedCommand::Text("€".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_init() {
+        let protocol = Protocol::new(Encoder::default());
+        let data = [
+            protocol.kanji_mode(true),
+            protocol.init(),
+            vec![0xD5],
+            protocol.unicode_mode(true),
+            protocol.init(),
+            vec![0xD5],
+        ]
+        .concat();
+
+        // The initialization cancels the Kanji and Unicode modes
+        assert_eq!(
+            commands(
+                Decoder::new(Some(PageCode::PC858)).with_kanji_encoding(KanjiEncoding::ShiftJis),
+                &data
+            ),
+            vec![
+                DecodedCommand::KanjiMode(true),
+                DecodedCommand::Init,
+                DecodedCommand::Text("€".to_string()),
+                DecodedCommand::UnicodeMode(true),
+                DecodedCommand::Init,
+                DecodedCommand::Text("€".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_unknown_and_truncated() {
+        let data = [b'a', ESC, b'#', 0x01, b'b', GS, b'(', b'k', 4, 0, 49];
+        let decoded = Decoder::default().decode(&data);
+
+        assert_eq!(
+            decoded,
+            vec![
+                DecodedInstruction {
+                    offset: 0,
+                    bytes: vec![b'a'],
+                    command: DecodedCommand::Text("a".to_string()),
+                },
+                DecodedInstruction {
+                    offset: 1,
+                    bytes: vec![ESC, b'#'],
+                    command: DecodedCommand::Unknown(vec![ESC, b'#']),
+                },
+                DecodedInstruction {
+                    offset: 3,
+                    bytes: vec![0x01],
+                    command: DecodedCommand::Unknown(vec![0x01]),
+                },
+                DecodedInstruction {
+                    offset: 4,
+                    bytes: vec![b'b'],
+                    command: DecodedCommand::Text("b".to_string()),
+                },
+                DecodedInstruction {
+                    offset: 5,
+                    bytes: vec![GS, b'(', b'k', 4, 0, 49],
+                    command: DecodedCommand::Truncated(vec![GS, b'(', b'k', 4, 0, 49]),
+                },
+            ]
+        );
+        assert!(decoded[1].command.is_error());
+        assert!(!decoded[0].command.is_error());
+
+        assert_eq!(
+            commands(Decoder::default(), &[ESC, b'E']),
+            vec![DecodedCommand::Truncated(vec![ESC, b'E'])]
+        );
+        assert_eq!(
+            commands(Decoder::default(), &[GS, b'v', b'0', 0, 2, 0, 2, 0, 0xFF]),
+            vec![DecodedCommand::Truncated(vec![GS, b'v', b'0', 0, 2, 0, 2, 0, 0xFF])]
+        );
+        assert_eq!(
+            commands(Decoder::default(), &[GS, b'8', b'L', 0xFF, 0xFF, 0xFF, 0xFF, 48, 112]),
+            vec![DecodedCommand::Truncated(vec![
+                GS, b'8', b'L', 0xFF, 0xFF, 0xFF, 0xFF, 48, 112
+            ])]
+        );
+    }
+}
@@ -0,0 +1,341 @@
+//! Indic scripts encoding for the India character sets (ESC R 66 to 82)
+
+use super::CharacterSet;
+use std::fmt;
+use unicode_normalization::char::decompose_canonical;
+
+const VIRAMA: u32 = 0x4D;
+const NUKTA: u32 = 0x3C;
+const AA_MATRA: u32 = 0x3E;
+const ZWNJ: char = '\u{200C}';
+const ZWJ: char = '\u{200D}';
+
+/// ISCII codes of the Devanagari characters
+///
+/// The other Indic Unicode blocks follow the Devanagari layout, their characters are looked up
+/// with the same offset in the block.
+const ISCII: [(char, &[u8]); 89] = [
+    ('\u{0901}', &[0xA1]),       // Candrabindu
+    ('\u{0902}', &[0xA2]),       // Anusvara
+    ('\u{0903}', &[0xA3]),       // Visarga
+    ('\u{0905}', &[0xA4]),       // A
+    ('\u{0906}', &[0xA5]),       // Aa
+    ('\u{0907}', &[0xA6]),       // I
+    ('\u{0908}', &[0xA7]),       // Ii
+    ('\u{0909}', &[0xA8]),       // U
+    ('\u{090A}', &[0xA9]),       // Uu
+    ('\u{090B}', &[0xAA]),       // Vocalic R
+    ('\u{090C}', &[0xA6, 0xE9]), // Vocalic L
+    ('\u{090D}', &[0xAE]),       // Candra E
+    ('\u{090E}', &[0xAB]),       // Short E
+    ('\u{090F}', &[0xAC]),       // E
+    ('\u{0910}', &[0xAD]),       // Ai
+    ('\u{0911}', &[0xB2]),       // Candra O
+    ('\u{0912}', &[0xAF]),       // Short O
+    ('\u{0913}', &[0xB0]),       // O
+    ('\u{0914}', &[0xB1]),       // Au
+    ('\u{0915}', &[0xB3]),       // Ka
+    ('\u{0916}', &[0xB4]),       // Kha
+    ('\u{0917}', &[0xB5]),       // Ga
+    ('\u{0918}', &[0xB6]),       // Gha
+    ('\u{0919}', &[0xB7]),       // Nga
+    ('\u{091A}', &[0xB8]),       // Ca
+    ('\u{091B}', &[0xB9]),       // Cha
+    ('\u{091C}', &[0xBA]),       // Ja
+    ('\u{091D}', &[0xBB]),       // Jha
+    ('\u{091E}', &[0xBC]),       // Nya
+    ('\u{091F}', &[0xBD]),       // Tta
+    ('\u{0920}', &[0xBE]),       // Ttha
+    ('\u{0921}', &[0xBF]),       // Dda
+    ('\u{0922}', &[0xC0]),       // Ddha
+    ('\u{0923}', &[0xC1]),       // Nna
+    ('\u{0924}', &[0xC2]),       // Ta
+    ('\u{0925}', &[0xC3]),       // Tha
+    ('\u{0926}', &[0xC4]),       // Da
+    ('\u{0927}', &[0xC5]),       // Dha
+    ('\u{0928}', &[0xC6]),       // Na
+    ('\u{0929}', &[0xC7]),       // Nnna
+    ('\u{092A}', &[0xC8]),       // Pa
+    ('\u{092B}', &[0xC9]),       // Pha
+    ('\u{092C}', &[0xCA]),       // Ba
+    ('\u{092D}', &[0xCB]),       // Bha
+    ('\u{092E}', &[0xCC]),       // Ma
+    ('\u{092F}', &[0xCD]),       // Ya
+    ('\u{0930}', &[0xCF]),       // Ra
+    ('\u{0931}', &[0xD0]),       // Rra
+    ('\u{0932}', &[0xD1]),       // La
+    ('\u{0933}', &[0xD2]),       // Lla
+    ('\u{0934}', &[0xD3]),       // Llla
+    ('\u{0935}', &[0xD4]),       // Va
+    ('\u{0936}', &[0xD5]),       // Sha
+    ('\u{0937}', &[0xD6]),       // Ssa
+    ('\u{0938}', &[0xD7]),       // Sa
+    ('\u{0939}', &[0xD8]),       // Ha
+    ('\u{093C}', &[0xE9]),       // Nukta
+    ('\u{093D}', &[0xEA, 0xE9]), // Avagraha
+    ('\u{093E}', &[0xDA]),       // Aa matra
+    ('\u{093F}', &[0xDB]),       // I matra
+    ('\u{0940}', &[0xDC]),       // Ii matra
+    ('\u{0941}', &[0xDD]),       // U matra
+    ('\u{0942}', &[0xDE]),       // Uu matra
+    ('\u{0943}', &[0xDF]),       // Vocalic R matra
+    ('\u{0944}', &[0xDF, 0xE9]), // Vocalic Rr matra
+    ('\u{0945}', &[0xE3]),       // Candra E matra
+    ('\u{0946}', &[0xE0]),       // Short E matra
+    ('\u{0947}', &[0xE1]),       // E matra
+    ('\u{0948}', &[0xE2]),       // Ai matra
+    ('\u{0949}', &[0xE7]),       // Candra O matra
+    ('\u{094A}', &[0xE4]),       // Short O matra
+    ('\u{094B}', &[0xE5]),       // O matra
+    ('\u{094C}', &[0xE6]),       // Au matra
+    ('\u{094D}', &[0xE8]),       // Virama (halant)
+    ('\u{0950}', &[0xA1, 0xE9]), // Om
+    ('\u{0958}', &[0xB3, 0xE9]), // Qa
+    ('\u{0959}', &[0xB4, 0xE9]), // Khha
+    ('\u{095A}', &[0xB5, 0xE9]), // Ghha
+    ('\u{095B}', &[0xBA, 0xE9]), // Za
+    ('\u{095C}', &[0xBF, 0xE9]), // Dddha
+    ('\u{095D}', &[0xC0, 0xE9]), // Rha
+    ('\u{095E}', &[0xC9, 0xE9]), // Fa
+    ('\u{095F}', &[0xCE]),       // Yya
+    ('\u{0960}', &[0xAA, 0xE9]), // Vocalic Rr
+    ('\u{0961}', &[0xA7, 0xE9]), // Vocalic Ll
+    ('\u{0962}', &[0xDB, 0xE9]), // Vocalic L matra
+    ('\u{0963}', &[0xDC, 0xE9]), // Vocalic Ll matra
+    ('\u{0964}', &[0xEA]),       // Danda
+    ('\u{0965}', &[0xEA, 0xEA]), // Double danda
+];
+
+/// Indic script of an India character set
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum IndicScript {
+    Devanagari,
+    Bengali,
+    Gurmukhi,
+    Gujarati,
+    Oriya,
+    Tamil,
+    Telugu,
+    Kannada,
+    Malayalam,
+}
+
+impl fmt::Display for IndicScript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndicScript::Devanagari => write!(f, "Devanagari"),
+            IndicScript::Bengali => write!(f, "Bengali"),
+            IndicScript::Gurmukhi => write!(f, "Gurmukhi"),
+            IndicScript::Gujarati => write!(f, "Gujarati"),
+            IndicScript::Oriya => write!(f, "Oriya"),
+            IndicScript::Tamil => write!(f, "Tamil"),
+            IndicScript::Telugu => write!(f, "Telugu"),
+            IndicScript::Kannada => write!(f, "Kannada"),
+            IndicScript::Malayalam => write!(f, "Malayalam"),
+        }
+    }
+}
+
+impl IndicScript {
+    /// Script of a character set (`None` for the non Indic character sets)
+    pub(crate) fn from_character_set(character_set: &CharacterSet) -> Option<Self> {
+        match character_set {
+            CharacterSet::IndiaDevanagari | CharacterSet::IndiaMarathi => Some(IndicScript::Devanagari),
+            CharacterSet::IndiaBengali | CharacterSet::IndiaAssamese => Some(IndicScript::Bengali),
+            CharacterSet::IndiaPunjabi => Some(IndicScript::Gurmukhi),
+            CharacterSet::IndiaGujarati => Some(IndicScript::Gujarati),
+            CharacterSet::IndiaOriya => Some(IndicScript::Oriya),
+            CharacterSet::IndiaTamil => Some(IndicScript::Tamil),
+            CharacterSet::IndiaTelugu => Some(IndicScript::Telugu),
+            CharacterSet::IndiaKannada => Some(IndicScript::Kannada),
+            CharacterSet::IndiaMalayalam => Some(IndicScript::Malayalam),
+            _ => None,
+        }
+    }
+
+    /// First code point of the Unicode block
+    fn block(&self) -> u32 {
+        match self {
+            IndicScript::Devanagari => 0x0900,
+            IndicScript::Bengali => 0x0980,
+            IndicScript::Gurmukhi => 0x0A00,
+            IndicScript::Gujarati => 0x0A80,
+            IndicScript::Oriya => 0x0B00,
+            IndicScript::Tamil => 0x0B80,
+            IndicScript::Telugu => 0x0C00,
+            IndicScript::Kannada => 0x0C80,
+            IndicScript::Malayalam => 0x0D00,
+        }
+    }
+
+    /// Offsets of the matras displayed before their consonant cluster
+    fn pre_base_matras(&self) -> &'static [u32] {
+        match self {
+            IndicScript::Devanagari | IndicScript::Gurmukhi | IndicScript::Gujarati => &[0x3F],
+            IndicScript::Bengali => &[0x3F, 0x47, 0x48],
+            IndicScript::Oriya => &[0x47],
+            IndicScript::Tamil | IndicScript::Malayalam => &[0x46, 0x47, 0x48],
+            IndicScript::Telugu | IndicScript::Kannada => &[],
+        }
+    }
+
+    /// Offset of a character in the Unicode block of the script
+    fn offset(&self, c: char) -> Option<u32> {
+        (c as u32).checked_sub(self.block()).filter(|offset| *offset < 0x80)
+    }
+
+    /// Check if a character is a consonant of the script
+    fn is_consonant(&self, c: char) -> bool {
+        match (self, c) {
+            (IndicScript::Bengali, '\u{09F0}' | '\u{09F1}') => true,
+            _ => self
+                .offset(c)
+                .is_some_and(|offset| matches!(offset, 0x15..=0x39 | 0x58..=0x5F)),
+        }
+    }
+
+    /// ISCII codes of a character
+    fn iscii(&self, c: char) -> Option<&'static [u8]> {
+        let devanagari = match (self, c) {
+            // Danda and double danda are shared by the scripts
+            (_, '\u{0964}' | '\u{0965}') => c,
+            // Assamese Ra and Wa
+            (IndicScript::Bengali, '\u{09F0}') => '\u{0930}',
+            (IndicScript::Bengali, '\u{09F1}') => '\u{0935}',
+            _ => char::from_u32(0x0900 + self.offset(c)?)?,
+        };
+
+        match devanagari {
+            '\u{0966}'..='\u{096F}' => DIGITS.get(devanagari as usize - 0x0966).copied(),
+            _ => ISCII.iter().find(|(c, _)| *c == devanagari).map(|(_, codes)| *codes),
+        }
+    }
+
+    /// Reorder the pre-base matras before their consonant cluster (visual order)
+    ///
+    /// Two-part vowel signs ending with an aa matra are split around the cluster (`கொ` → `ெ` `க` `ா`).
+    pub(crate) fn visual_order(&self, text: &str) -> Vec<char> {
+        let mut chars = Vec::with_capacity(text.len());
+        let mut cluster: Option<usize> = None;
+
+        for c in text.chars() {
+            let offset = self.offset(c);
+            let mut parts = vec![];
+            decompose_canonical(c, |part| parts.push(part));
+
+            match (cluster, parts.as_slice()) {
+                (Some(start), [matra]) if offset.is_some_and(|o| self.pre_base_matras().contains(&o)) => {
+                    chars.insert(start, *matra);
+                    cluster = None;
+                }
+                (Some(start), [pre, post])
+                    if self.offset(*pre).is_some_and(|o| self.pre_base_matras().contains(&o))
+                        && self.offset(*post) == Some(AA_MATRA) =>
+                {
+                    chars.insert(start, *pre);
+                    chars.push(*post);
+                    cluster = None;
+                }
+                _ => {
+                    let joined = chars.last().is_some_and(|&last| {
+                        self.offset(last) == Some(VIRAMA) || ((last == ZWJ || last == ZWNJ) && cluster.is_some())
+                    });
+
+                    if self.is_consonant(c) {
+                        if !joined || cluster.is_none() {
+                            cluster = Some(chars.len());
+                        }
+                    } else if !(offset == Some(NUKTA) || offset == Some(VIRAMA) || c == ZWJ || c == ZWNJ) {
+                        cluster = None;
+                    }
+                    chars.push(c);
+                }
+            }
+        }
+
+        chars
+    }
+
+    /// Encode a character, or a virama followed by a joiner (explicit and soft halant)
+    ///
+    /// Returns the ISCII codes with the number of characters encoded, `None` if the character is missing.
+    pub(crate) fn encode(&self, chars: &[char]) -> Option<(Vec<u8>, usize)> {
+        match chars {
+            [virama, ZWNJ, ..] if self.offset(*virama) == Some(VIRAMA) => Some((vec![0xE8, 0xE8], 2)),
+            [virama, ZWJ, ..] if self.offset(*virama) == Some(VIRAMA) => Some((vec![0xE8, 0xE9], 2)),
+            [ZWJ | ZWNJ, ..] => Some((vec![], 1)),
+            [c, ..] => self.iscii(*c).map(|codes| (codes.to_vec(), 1)),
+            [] => None,
+        }
+    }
+}
+
+/// ISCII codes of the digits
+const DIGITS: [&[u8]; 10] = [
+    &[0xF1],
+    &[0xF2],
+    &[0xF3],
+    &[0xF4],
+    &[0xF5],
+    &[0xF6],
+    &[0xF7],
+    &[0xF8],
+    &[0xF9],
+    &[0xFA],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(script: IndicScript, text: &str) -> Vec<u8> {
+        let chars = script.visual_order(text);
+        let mut bytes = vec![];
+        let mut i = 0;
+        while i < chars.len() {
+            let (codes, length) = match chars[i].is_ascii() {
+                true => (vec![chars[i] as u8], 1),
+                false => script.encode(&chars[i..]).unwrap(),
+            };
+            bytes.extend(codes);
+            i += length;
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_devanagari() {
+        assert_eq!(
+            encode(IndicScript::Devanagari, "नमस्ते"),
+            vec![0xC6, 0xCC, 0xD7, 0xE8, 0xC2, 0xE1]
+        );
+        assert_eq!(
+            encode(IndicScript::Devanagari, "हिंदी"),
+            vec![0xDB, 0xD8, 0xA2, 0xC4, 0xDC]
+        );
+        assert_eq!(
+            encode(IndicScript::Devanagari, "स्थिति"),
+            vec![0xDB, 0xD7, 0xE8, 0xC3, 0xDB, 0xC2]
+        );
+        assert_eq!(
+            encode(IndicScript::Devanagari, "क़ १०।"),
+            vec![0xB3, 0xE9, b' ', 0xF2, 0xF1, 0xEA]
+        );
+        assert_eq!(
+            encode(IndicScript::Devanagari, "क्\u{200C}ष"),
+            vec![0xB3, 0xE8, 0xE8, 0xD6]
+        );
+    }
+
+    #[test]
+    fn test_other_scripts() {
+        assert_eq!(encode(IndicScript::Tamil, "கொ"), vec![0xE0, 0xB3, 0xDA]);
+        assert_eq!(
+            encode(IndicScript::Tamil, "வணக்கம்"),
+            vec![0xD4, 0xC1, 0xB3, 0xE8, 0xB3, 0xCC, 0xE8]
+        );
+        assert_eq!(encode(IndicScript::Bengali, "কি"), vec![0xDB, 0xB3]);
+        assert_eq!(encode(IndicScript::Telugu, "తె"), vec![0xC2, 0xE0]);
+        assert_eq!(IndicScript::Tamil.iscii('क'), None);
+    }
+}
@@ -1,21 +1,42 @@
+mod bidi;
 mod bit_image;
 mod character;
+mod code_page;
 mod codes;
+mod combining;
 pub(crate) mod common;
 mod constants;
+mod decoder;
 mod graphics;
+pub(crate) mod indic;
 mod page_codes;
+mod profile;
 mod protocol;
+mod renderer;
+mod state;
 mod status;
+mod style;
+mod table;
+mod transliteration;
 mod types;
+mod width;
 
 #[cfg(feature = "graphics")]
 pub use bit_image::*;
 pub use character::*;
+pub use code_page::*;
 pub use codes::*;
 pub use constants::*;
+pub use decoder::*;
 #[cfg(feature = "graphics")]
 pub use graphics::*;
+pub use profile::*;
 pub use protocol::*;
+#[cfg(feature = "graphics")]
+pub use renderer::*;
+pub use state::*;
 pub use status::*;
+pub use style::*;
+pub use table::*;
 pub use types::*;
+pub use width::*;
@@ -1,130 +1,83 @@
 //! List of page codes
 
-use crate::domain::PageCode;
-use crate::errors::PrinterError;
-use lazy_static::lazy_static;
-use std::collections::HashMap;
-use std::iter::{IntoIterator, Iterator};
-
-/// Page codes table list
-#[derive(Debug, Clone, Copy)]
-pub(crate) enum PageCodeTable {
-    PC437,
-    Katakana,
-    PC850,
-    PC852,
-    PC858,
-    PC860,
-    PC863,
-    PC865,
-    PC851,
-    PC853,
-    PC857,
-    PC737,
-    ISO8859_2,
-    ISO8859_7,
-    ISO8859_15,
-    WPC1252,
-    PC866,
-    WPC775,
-    PC855,
-    PC861,
-    PC862,
-    PC869,
-    PC1118,
-    PC1119,
-    PC1125,
-    WPC1250,
-    WPC1251,
-    WPC1253,
-    WPC1254,
-    WPC1257,
-    KZ1048,
+use super::{code_page::index, CodePage, PageCode};
+
+/// Built-in code page of a page code
+pub(crate) fn code_page(page_code: PageCode) -> &'static CodePage {
+    match page_code {
+        PageCode::PC437 => &PC437_TABLE,
+        PageCode::Katakana => &KATAKANA_TABLE,
+        PageCode::Hiragana => &HIRAGANA_TABLE,
+        PageCode::PC850 => &PC850_TABLE,
+        PageCode::PC852 => &PC852_TABLE,
+        PageCode::PC858 => &PC858_TABLE,
+        PageCode::PC874 => &PC874_TABLE,
+        PageCode::PC860 => &PC860_TABLE,
+        PageCode::PC863 => &PC863_TABLE,
+        PageCode::PC865 => &PC865_TABLE,
+        PageCode::PC851 => &PC851_TABLE,
+        PageCode::PC853 => &PC853_TABLE,
+        PageCode::PC857 => &PC857_TABLE,
+        PageCode::PC737 => &PC737_TABLE,
+        PageCode::ISO8859_2 => &ISO8859_2_TABLE,
+        PageCode::ISO8859_7 => &ISO8859_7_TABLE,
+        PageCode::ISO8859_15 => &ISO8859_15_TABLE,
+        PageCode::WPC1252 => &WPC1252_TABLE,
+        PageCode::PC866 => &PC866_TABLE,
+        PageCode::WPC775 => &WPC775_TABLE,
+        PageCode::PC855 => &PC855_TABLE,
+        PageCode::PC861 => &PC861_TABLE,
+        PageCode::PC862 => &PC862_TABLE,
+        PageCode::PC869 => &PC869_TABLE,
+        PageCode::PC1118 => &PC1118_TABLE,
+        PageCode::PC1119 => &PC1119_TABLE,
+        PageCode::PC1125 => &PC1125_TABLE,
+        PageCode::WPC1250 => &WPC1250_TABLE,
+        PageCode::WPC1251 => &WPC1251_TABLE,
+        PageCode::WPC1253 => &WPC1253_TABLE,
+        PageCode::WPC1254 => &WPC1254_TABLE,
+        PageCode::WPC1257 => &WPC1257_TABLE,
+        PageCode::KZ1048 => &KZ1048_TABLE,
+        PageCode::PC720 => &PC720_TABLE,
+        PageCode::PC864 => &PC864_TABLE,
+        PageCode::PC1098 => &PC1098_TABLE,
+        PageCode::WPC1255 => &WPC1255_TABLE,
+        PageCode::WPC1256 => &WPC1256_TABLE,
+        PageCode::WPC1258 => &WPC1258_TABLE,
+    }
 }
 
-impl PageCodeTable {
-    /// Get the table for the page code
-    pub(crate) fn get_table(&self) -> &HashMap<char, u8> {
-        match self {
-            Self::PC437 => &PC437_TABLE,
-            Self::Katakana => &KATAKANA_TABLE,
-            Self::PC850 => &PC850_TABLE,
-            Self::PC852 => &PC852_TABLE,
-            Self::PC858 => &PC858_TABLE,
-            Self::PC860 => &PC860_TABLE,
-            Self::PC863 => &PC863_TABLE,
-            Self::PC865 => &PC865_TABLE,
-            Self::PC851 => &PC851_TABLE,
-            Self::PC853 => &PC853_TABLE,
-            Self::PC857 => &PC857_TABLE,
-            Self::PC737 => &PC737_TABLE,
-            Self::ISO8859_2 => &ISO8859_2_TABLE,
-            Self::ISO8859_7 => &ISO8859_7_TABLE,
-            Self::ISO8859_15 => &ISO8859_15_TABLE,
-            Self::WPC1252 => &WPC1252_TABLE,
-            Self::PC866 => &PC866_TABLE,
-            Self::WPC775 => &WPC775_TABLE,
-            Self::PC855 => &PC855_TABLE,
-            Self::PC861 => &PC861_TABLE,
-            Self::PC862 => &PC862_TABLE,
-            Self::PC869 => &PC869_TABLE,
-            Self::PC1118 => &PC1118_TABLE,
-            Self::PC1119 => &PC1119_TABLE,
-            Self::PC1125 => &PC1125_TABLE,
-            Self::WPC1250 => &WPC1250_TABLE,
-            Self::WPC1251 => &WPC1251_TABLE,
-            Self::WPC1253 => &WPC1253_TABLE,
-            Self::WPC1254 => &WPC1254_TABLE,
-            Self::WPC1257 => &WPC1257_TABLE,
-            Self::KZ1048 => &KZ1048_TABLE,
-        }
+/// Full table from the characters of the bytes `0x80`-`0xFF`
+const fn upper_half(chars: [char; 128]) -> [char; 256] {
+    let mut table = ['\0'; 256];
+    let mut i = 0;
+    while i < chars.len() {
+        table[0x80 + i] = chars[i];
+        i += 1;
     }
+    table
 }
 
-impl TryFrom<PageCode> for PageCodeTable {
-    type Error = PrinterError;
-
-    fn try_from(value: PageCode) -> Result<Self, Self::Error> {
-        match value {
-            PageCode::PC437 => Ok(Self::PC437),
-            PageCode::Katakana => Ok(Self::Katakana),
-            PageCode::PC850 => Ok(Self::PC850),
-            PageCode::PC852 => Ok(Self::PC852),
-            PageCode::PC858 => Ok(Self::PC858),
-            PageCode::PC860 => Ok(Self::PC860),
-            PageCode::PC863 => Ok(Self::PC863),
-            PageCode::PC865 => Ok(Self::PC865),
-            PageCode::PC851 => Ok(Self::PC851),
-            PageCode::PC853 => Ok(Self::PC853),
-            PageCode::PC857 => Ok(Self::PC857),
-            PageCode::PC737 => Ok(Self::PC737),
-            PageCode::ISO8859_2 => Ok(Self::ISO8859_2),
-            PageCode::ISO8859_7 => Ok(Self::ISO8859_7),
-            PageCode::ISO8859_15 => Ok(Self::ISO8859_15),
-            PageCode::WPC1252 => Ok(Self::WPC1252),
-            PageCode::PC866 => Ok(Self::PC866),
-            PageCode::WPC775 => Ok(Self::WPC775),
-            PageCode::PC855 => Ok(Self::PC855),
-            PageCode::PC861 => Ok(Self::PC861),
-            PageCode::PC862 => Ok(Self::PC862),
-            PageCode::PC869 => Ok(Self::PC869),
-            PageCode::PC1118 => Ok(Self::PC1118),
-            PageCode::PC1119 => Ok(Self::PC1119),
-            PageCode::PC1125 => Ok(Self::PC1125),
-            PageCode::WPC1250 => Ok(Self::WPC1250),
-            PageCode::WPC1251 => Ok(Self::WPC1251),
-            PageCode::WPC1253 => Ok(Self::WPC1253),
-            PageCode::WPC1254 => Ok(Self::WPC1254),
-            PageCode::WPC1257 => Ok(Self::WPC1257),
-            PageCode::KZ1048 => Ok(Self::KZ1048),
-            _ => Err(PrinterError::Input(format!("no table for this page code: {value}"))),
-        }
-    }
+/// Built-in code page with its table and index computed at compile time
+///
+/// The table contains the characters of the bytes `0x80`-`0xFF`, with `'\0'` for the bytes without character.
+macro_rules! builtin_code_page {
+    ($(#[$doc:meta])* $name:ident, $page_code:ident, $number:literal, $chars:expr) => {
+        $(#[$doc])*
+        static $name: CodePage = {
+            const TABLE: [char; 256] = upper_half($chars);
+            const INDEX: [(char, u8); 256] = index(&TABLE);
+            CodePage::builtin(PageCode::$page_code, $number, &TABLE, &INDEX)
+        };
+    };
 }
 
-lazy_static! {
+builtin_code_page!(
     /// PC437 Page code table
-    static ref PC437_TABLE: HashMap<char, u8> = [
+    PC437_TABLE,
+    PC437,
+    0,
+    [
         'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
         'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
         'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
@@ -134,23 +87,31 @@ lazy_static! {
         'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
         '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// Katakana Page code table (CP932 or IBM-932)
-    static ref KATAKANA_TABLE: HashMap<char, u8> = [
-             '｡', '｢', '｣', '､', '･', 'ｦ', 'ｧ', 'ｨ', 'ｩ', 'ｪ', 'ｫ', 'ｬ', 'ｭ', 'ｮ', 'ｯ',
+    KATAKANA_TABLE,
+    Katakana,
+    1,
+    [
+        '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+        '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+        '\0', '｡', '｢', '｣', '､', '･', 'ｦ', 'ｧ', 'ｨ', 'ｩ', 'ｪ', 'ｫ', 'ｬ', 'ｭ', 'ｮ', 'ｯ',
         'ｰ', 'ｱ', 'ｲ', 'ｳ', 'ｴ', 'ｵ', 'ｶ', 'ｷ', 'ｸ', 'ｹ', 'ｺ', 'ｻ', 'ｼ', 'ｽ', 'ｾ', 'ｿ',
         'ﾀ', 'ﾁ', 'ﾂ', 'ﾃ', 'ﾄ', 'ﾅ', 'ﾆ', 'ﾇ', 'ﾈ', 'ﾉ', 'ﾊ', 'ﾋ', 'ﾌ', 'ﾍ', 'ﾎ', 'ﾏ',
         'ﾐ', 'ﾑ', 'ﾒ', 'ﾓ', 'ﾔ', 'ﾕ', 'ﾖ', 'ﾗ', 'ﾘ', 'ﾙ', 'ﾚ', 'ﾛ', 'ﾜ', 'ﾝ', 'ﾞ', 'ﾟ',
+        '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+        '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0xA1) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC850 Page code table
-    static ref PC850_TABLE: HashMap<char, u8> = [
+    PC850_TABLE,
+    PC850,
+    2,
+    [
         'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
         'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', 'ø', '£', 'Ø', '×', 'ƒ',
         'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '®', '¬', '½', '¼', '¡', '«', '»',
@@ -160,12 +121,14 @@ lazy_static! {
         'Ó', 'ß', 'Ô', 'Ò', 'õ', 'Õ', 'µ', 'þ', 'Þ', 'Ú', 'Û', 'Ù', 'ý', 'Ý', '¯', '´',
         '-', '±', '‗', '¾', '¶', '§', '÷', '¸', '°', '¨', '·', '¹', '³', '²', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC863 Page code table
-    static ref PC863_TABLE: HashMap<char, u8> = [
+    PC863_TABLE,
+    PC863,
+    4,
+    [
         'Ç', 'ü', 'é', 'â', 'Â', 'à', '¶', 'ç', 'ê', 'ë', 'è', 'ï', 'î', '‗', 'À', '§',
         'É', 'È', 'Ê', 'ô', 'Ë', 'Ï', 'û', 'ù', '¤', 'Ô', 'Ü', '¢', '£', 'Ù', 'Û', 'ƒ',
         '¦', '´', 'ó', 'ú', '¨', '¸', '³', '¯', 'Î', '⌐', '¬', '½', '¼', '¾', '«', '»',
@@ -175,12 +138,14 @@ lazy_static! {
         'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
         '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC852 Page code table
-    static ref PC852_TABLE: HashMap<char, u8> = [
+    PC852_TABLE,
+    PC852,
+    18,
+    [
         'Ç', 'ü', 'é', 'â', 'ä', 'ů', 'ć', 'ç', 'ł', 'ë', 'Ő', 'ő', 'î', 'Ź', 'Ä', 'Ć',
         'É', 'Ĺ', 'ĺ', 'ô', 'ö', 'Ľ', 'ľ', 'Ś', 'ś', 'Ö', 'Ü', 'Ť', 'ť', 'Ł', '×', 'č',
         'á', 'í', 'ó', 'ú', 'Ą', 'ą', 'Ž', 'ž', 'Ę', 'ę', '¬', 'ź', 'Č', 'ş', '«', '»',
@@ -188,28 +153,50 @@ lazy_static! {
         '└', '┴', '┬', '├', '─', '┼', 'Ă', 'ă', '╚', '╔', '╩', '╦', '╠', '═', '╬', '¤',
         'đ', 'Đ', 'Ď', 'Ë', 'ď', 'Ň', 'Í', 'Î', 'ě', '┘', '┌', '█', '▄', 'Ţ', 'Ů', '▀',
         'Ó', 'ß', 'Ô', 'Ń', 'ń', 'ň', 'Š', 'š', 'Ŕ', 'Ú', 'ŕ', 'Ű', 'ý', 'Ý', 'ţ', '´',
-        '\u{AD}', '˝', '˛', 'ˇ', '˘', '§', '÷', '¸', '°', '¨', '˙', 'ű', 'Ř', 'ř', '■', '\u{00A0}',
+        '\u{00AD}', '˝', '˛', 'ˇ', '˘', '§', '÷', '¸', '°', '¨', '˙', 'ű', 'Ř', 'ř', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC858 Page code table
-    static ref PC858_TABLE: HashMap<char, u8> = [
+    PC858_TABLE,
+    PC858,
+    19,
+    [
         'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
         'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', 'ø', '£', 'Ø', '×', 'ƒ',
-        'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '®', '⌐', '¬', '½', '¼', '¡', '«', '»',
+        'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '®', '¬', '½', '¼', '¡', '«', '»',
         '░', '▒', '▓', '│', '┤', 'Á', 'Â', 'À', '©', '╣', '║', '╗', '╝', '¢', '¥', '┐',
         '└', '┴', '┬', '├', '─', '┼', 'ã', 'Ã', '╚', '╔', '╩', '╦', '╠', '═', '╬', '¤',
         'ð', 'Ð', 'Ê', 'Ë', 'È', '€', 'Í', 'Î', 'Ï', '┘', '┌', '█', '▄', '¦', 'Ì', '▀',
-        'Ó', 'ß', 'Ô', 'Ô', 'õ', 'Õ', 'µ', 'þ', 'Þ', 'Ú', 'Û', 'Ù', 'ý', 'Ý', '¯', '´',
-        '-', '±', '‗', '¾', '¶', '§', '÷', '¸', '°', '¨', '·', '¹', '³', '²', '■', '\u{00A0}']
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+        'Ó', 'ß', 'Ô', 'Ò', 'õ', 'Õ', 'µ', 'þ', 'Þ', 'Ú', 'Û', 'Ù', 'ý', 'Ý', '¯', '´',
+        '-', '±', '‗', '¾', '¶', '§', '÷', '¸', '°', '¨', '·', '¹', '³', '²', '■', '\u{00A0}',
+    ]
+);
+
+builtin_code_page!(
+    /// PC874 Page code table (Thai Character Code 11, TIS-620 with Windows extensions)
+    PC874_TABLE,
+    PC874,
+    21,
+    [
+        '€', '\0', '\0', '\0', '\0', '…', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+        '\0', '‘', '’', '“', '”', '•', '–', '—', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+        '\u{00A0}', 'ก', 'ข', 'ฃ', 'ค', 'ฅ', 'ฆ', 'ง', 'จ', 'ฉ', 'ช', 'ซ', 'ฌ', 'ญ', 'ฎ', 'ฏ',
+        'ฐ', 'ฑ', 'ฒ', 'ณ', 'ด', 'ต', 'ถ', 'ท', 'ธ', 'น', 'บ', 'ป', 'ผ', 'ฝ', 'พ', 'ฟ',
+        'ภ', 'ม', 'ย', 'ร', 'ฤ', 'ล', 'ฦ', 'ว', 'ศ', 'ษ', 'ส', 'ห', 'ฬ', 'อ', 'ฮ', 'ฯ',
+        'ะ', '\u{0E31}', 'า', 'ำ', '\u{0E34}', '\u{0E35}', '\u{0E36}', '\u{0E37}', '\u{0E38}', '\u{0E39}', '\u{0E3A}', '\0', '\0', '\0', '\0', '฿',
+        'เ', 'แ', 'โ', 'ใ', 'ไ', 'ๅ', 'ๆ', '\u{0E47}', '\u{0E48}', '\u{0E49}', '\u{0E4A}', '\u{0E4B}', '\u{0E4C}', '\u{0E4D}', '\u{0E4E}', '๏',
+        '๐', '๑', '๒', '๓', '๔', '๕', '๖', '๗', '๘', '๙', '๚', '๛', '\0', '\0', '\0', '\0',
+    ]
+);
 
+builtin_code_page!(
     /// PC860 Page code table
-    static ref PC860_TABLE: HashMap<char, u8> = [
+    PC860_TABLE,
+    PC860,
+    3,
+    [
         'Ç', 'ü', 'é', 'â', 'ã', 'à', 'Á', 'ç', 'ê', 'Ê', 'è', 'Í', 'Ô', 'ì', 'Ã', 'Â',
         'É', 'À', 'È', 'ô', 'õ', 'ò', 'Ú', 'ù', 'Ì', 'Õ', 'Ü', '¢', '£', 'Ù', '₧', 'Ó',
         'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', 'Ò', '¬', '½', '¼', '¡', '«', '»',
@@ -219,12 +206,14 @@ lazy_static! {
         'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
         '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC865 Page code table
-    static ref PC865_TABLE: HashMap<char, u8> = [
+    PC865_TABLE,
+    PC865,
+    5,
+    [
         'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
         'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', 'ø', '£', 'Ø', '₧', 'ƒ',
         'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '¤',
@@ -234,13 +223,14 @@ lazy_static! {
         'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
         '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC851 Page code table
-    /// Uses '\0' as placeholder for empty spots
-    static ref PC851_TABLE: HashMap<char, u8> = [
+    PC851_TABLE,
+    PC851,
+    11,
+    [
         'Ç', 'ü', 'é', 'â', 'ä', 'à', 'Ά', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'Έ', 'Ä', 'Ή',
         'Ί', '\0', 'Ό', 'ô', 'ö', 'Ύ', 'û', 'ù', 'Ώ', 'Ö', 'Ü', 'ά', '£', 'έ', 'ή', 'ί',
         'ϊ', 'ΐ', 'ό', 'ύ', 'Α', 'Β', 'Γ', 'Δ', 'Ε', 'Ζ', 'Η', '½', 'Θ', 'Ι', '«', '»',
@@ -250,14 +240,14 @@ lazy_static! {
         'ζ', 'η', 'θ', 'ι', 'κ', 'λ', 'μ', 'ν', 'ξ', 'ο', 'π', 'ρ', 'σ', 'ς', 'τ', '´',
         '-', '±', 'υ', 'φ', 'χ', '§', 'ψ', '¸', '°', '¨', 'ω', 'ϋ', 'ΰ', 'ώ', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .filter(|(_, c)| *c != '\0')
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC853 Page code table
-    /// Uses '\0' as placeholder for empty spots
-    static ref PC853_TABLE: HashMap<char, u8> = [
+    PC853_TABLE,
+    PC853,
+    12,
+    [
         'Ç', 'ü', 'é', 'â', 'ä', 'à', 'ĉ', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Ĉ',
         'É', 'ċ', 'Ċ', 'ô', 'ö', 'ò', 'û', 'ù', 'İ', 'Ö', 'Ü', 'ĝ', '£', 'Ĝ', '×', 'ĵ',
         'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'Ğ', 'ğ', 'Ĥ', 'ĥ', '\0', '½', 'Ĵ', 'ş', '«', '»',
@@ -267,14 +257,14 @@ lazy_static! {
         'Ó', 'ß', 'Ô', 'Ò', 'Ġ', 'ġ', 'µ', 'Ħ', 'ħ', 'Ú', 'Û', 'Ù', 'Ŭ', 'ŭ', '·', '´',
         '-', '\0', 'ℓ', 'ŉ', '˘', '§', '÷', '¸', '°', '¨', '˙', '\0', '³', '²', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .filter(|(_, c)| *c != '\0')
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC857 Page code table
-    /// Uses '\0' as placeholder for empty spots
-    static ref PC857_TABLE: HashMap<char, u8> = [
+    PC857_TABLE,
+    PC857,
+    13,
+    [
         'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ı', 'Ä', 'Å',
         'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'İ', 'Ö', 'Ü', 'ø', '£', 'Ø', 'Ş', 'ş',
         'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'Ğ', 'ğ', '¿', '®', '¬', '½', '¼', '¡', '«', '»',
@@ -284,13 +274,14 @@ lazy_static! {
         'Ó', 'ß', 'Ô', 'Ò', 'õ', 'Õ', 'µ', '.', '×', 'Ú', 'Û', 'Ù', 'ì', 'ÿ', '¯', '´',
         '-', '±', '\0', '¾', '¶', '§', '÷', '¸', '°', '¨', '·', '¹', '³', '²', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .filter(|(_, c)| *c != '\0')
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC737 Page code table
-    static ref PC737_TABLE: HashMap<char, u8> = [
+    PC737_TABLE,
+    PC737,
+    14,
+    [
         'Α', 'Β', 'Γ', 'Δ', 'Ε', 'Ζ', 'Η', 'Θ', 'Ι', 'Κ', 'Λ', 'Μ', 'Ν', 'Ξ', 'Ο', 'Π',
         'Ρ', 'Σ', 'Τ', 'Υ', 'Φ', 'Χ', 'Ψ', 'Ω', 'α', 'β', 'γ', 'δ', 'ε', 'ζ', 'η', 'θ',
         'ι', 'κ', 'λ', 'μ', 'ν', 'ξ', 'ο', 'π', 'ρ', 'σ', 'ς', 'τ', 'υ', 'φ', 'χ', 'ψ',
@@ -300,63 +291,65 @@ lazy_static! {
         'ω', 'ά', 'έ', 'ή', 'ϊ', 'ί', 'ό', 'ύ', 'ϋ', 'ώ', 'Ά', 'Έ', 'Ή', 'Ί', 'Ό', 'Ύ',
         'Ώ', '±', '≥', '≤', 'Ϊ', 'Ϋ', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// ISO8859_2 Page code table
-    static ref ISO8859_2_TABLE: HashMap<char, u8> = [
-        '\u{00A0}', // NO-BREAK SPACE
-        'Ą', '˘', 'Ł', '¤', 'Ľ', 'Ś', '§', '¨', 'Š', 'Ş', 'Ť', 'Ź',
-        '\u{00AD}', // SOFT HYPHEN
-        'Ž', 'Ż',
+    ISO8859_2_TABLE,
+    ISO8859_2,
+    39,
+    [
+        '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+        '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+        '\u{00A0}', 'Ą', '˘', 'Ł', '¤', 'Ľ', 'Ś', '§', '¨', 'Š', 'Ş', 'Ť', 'Ź', '\u{00AD}', 'Ž', 'Ż',
         '°', 'ą', '˛', 'ł', '´', 'ľ', 'ś', 'ˇ', '¸', 'š', 'ş', 'ť', 'ź', '˝', 'ž', 'ż',
         'Ŕ', 'Á', 'Â', 'Ă', 'Ä', 'Ĺ', 'Ć', 'Ç', 'Č', 'É', 'Ę', 'Ë', 'Ě', 'Í', 'Î', 'Ď',
         'Đ', 'Ń', 'Ň', 'Ó', 'Ô', 'Ő', 'Ö', '×', 'Ř', 'Ů', 'Ú', 'Ű', 'Ü', 'Ý', 'Ţ', 'ß',
         'ŕ', 'á', 'â', 'ă', 'ä', 'ĺ', 'ć', 'ç', 'č', 'é', 'ę', 'ë', 'ě', 'í', 'î', 'ď',
         'đ', 'ń', 'ň', 'ó', 'ô', 'ő', 'ö', '÷', 'ř', 'ů', 'ú', 'ű', 'ü', 'ý', 'ţ', '˙',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0xA0) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// ISO8859_7 Page code table
-    /// Uses '\0' as placeholder for empty spots
-    static ref ISO8859_7_TABLE: HashMap<char, u8> = [
-        '\u{00A0}', // NO-BREAK SPACE
-        '‘', '’', '£', '€', '₯', '¦', '§', '¨', '©', 'ͺ', '«', '¬',
-        '\u{00AD}', // SOFT HYPHEN
-        '\0', '―',
+    ISO8859_7_TABLE,
+    ISO8859_7,
+    15,
+    [
+        '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+        '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+        '\u{00A0}', '‘', '’', '£', '€', '₯', '¦', '§', '¨', '©', 'ͺ', '«', '¬', '\u{00AD}', '\0', '―',
         '°', '±', '²', '³', '΄', '΅', 'Ά', '·', 'Έ', 'Ή', 'Ί', '»', 'Ό', '½', 'Ύ', 'Ώ',
         'ΐ', 'Α', 'Β', 'Γ', 'Δ', 'Ε', 'Ζ', 'Η', 'Θ', 'Ι', 'Κ', 'Λ', 'Μ', 'Ν', 'Ξ', 'Ο',
         'Π', 'Ρ', '\0', 'Σ', 'Τ', 'Υ', 'Φ', 'Χ', 'Ψ', 'Ω', 'Ϊ', 'Ϋ', 'ά', 'έ', 'ή', 'ί',
         'ΰ', 'α', 'β', 'γ', 'δ', 'ε', 'ζ', 'η', 'θ', 'ι', 'κ', 'λ', 'μ', 'ν', 'ξ', 'ο',
-        'π', 'ρ', 'ς', 'σ', 'τ', 'υ', 'φ', 'χ', 'ψ', 'ω', 'ϊ', 'ϋ', 'ό', 'ύ', 'ώ',
+        'π', 'ρ', 'ς', 'σ', 'τ', 'υ', 'φ', 'χ', 'ψ', 'ω', 'ϊ', 'ϋ', 'ό', 'ύ', 'ώ', '\0',
     ]
-    .into_iter().enumerate()
-    .filter(|(_, c)| *c != '\0')
-    .map(|(i, c)| (c, (i + 0xA0) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// ISO8859_15 Page code table
-    static ref ISO8859_15_TABLE: HashMap<char, u8> = [
-        '\u{00A0}', // NO-BREAK SPACE
-        '¡', '¢', '£', '€', '¥', 'Š', '§', 'š', '©', 'ª', '«', '¬',
-        '\u{00AD}', // SOFT HYPHEN
-        '®', '¯',
+    ISO8859_15_TABLE,
+    ISO8859_15,
+    40,
+    [
+        '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+        '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+        '\u{00A0}', '¡', '¢', '£', '€', '¥', 'Š', '§', 'š', '©', 'ª', '«', '¬', '\u{00AD}', '®', '¯',
         '°', '±', '²', '³', 'Ž', 'µ', '¶', '·', 'ž', '¹', 'º', '»', 'Œ', 'œ', 'Ÿ', '¿',
         'À', 'Á', 'Â', 'Ã', 'Ä', 'Å', 'Æ', 'Ç', 'È', 'É', 'Ê', 'Ë', 'Ì', 'Í', 'Î', 'Ï',
         'Ð', 'Ñ', 'Ò', 'Ó', 'Ô', 'Õ', 'Ö', '×', 'Ø', 'Ù', 'Ú', 'Û', 'Ü', 'Ý', 'Þ', 'ß',
         'à', 'á', 'â', 'ã', 'ä', 'å', 'æ', 'ç', 'è', 'é', 'ê', 'ë', 'ì', 'í', 'î', 'ï',
         'ð', 'ñ', 'ò', 'ó', 'ô', 'õ', 'ö', '÷', 'ø', 'ù', 'ú', 'û', 'ü', 'ý', 'þ', 'ÿ',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0xA0) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// WPC1252 Page code table
-    /// Uses '\0' as placeholder for empty spots
-    static ref WPC1252_TABLE: HashMap<char, u8> = [
+    WPC1252_TABLE,
+    WPC1252,
+    16,
+    [
         '€', '\0', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\0', 'Ž', '\0',
         '\0', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\0', 'ž', 'Ÿ',
         '\u{00A0}', '¡', '¢', '£', '¤', '¥', '¦', '§', '¨', '©', 'ª', '«', '¬', '\u{00AD}', '®', '¯',
@@ -366,13 +359,14 @@ lazy_static! {
         'à', 'á', 'â', 'ã', 'ä', 'å', 'æ', 'ç', 'è', 'é', 'ê', 'ë', 'ì', 'í', 'î', 'ï',
         'ð', 'ñ', 'ò', 'ó', 'ô', 'õ', 'ö', '÷', 'ø', 'ù', 'ú', 'û', 'ü', 'ý', 'þ', 'ÿ',
     ]
-    .into_iter().enumerate()
-    .filter(|(_, c)| *c != '\0')
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC866 Page code table
-    static ref PC866_TABLE: HashMap<char, u8> = [
+    PC866_TABLE,
+    PC866,
+    17,
+    [
         'А', 'Б', 'В', 'Г', 'Д', 'Е', 'Ж', 'З', 'И', 'Й', 'К', 'Л', 'М', 'Н', 'О', 'П',
         'Р', 'С', 'Т', 'У', 'Ф', 'Х', 'Ц', 'Ч', 'Ш', 'Щ', 'Ъ', 'Ы', 'Ь', 'Э', 'Ю', 'Я',
         'а', 'б', 'в', 'г', 'д', 'е', 'ж', 'з', 'и', 'й', 'к', 'л', 'м', 'н', 'о', 'п',
@@ -382,12 +376,14 @@ lazy_static! {
         'р', 'с', 'т', 'у', 'ф', 'х', 'ц', 'ч', 'ш', 'щ', 'ъ', 'ы', 'ь', 'э', 'ю', 'я',
         'Ё', 'ё', 'Є', 'є', 'Ї', 'ї', 'Ў', 'ў', '°', '∙', '·', '√', '№', '¤', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// WPC775 Page code table
-    static ref WPC775_TABLE: HashMap<char, u8> = [
+    WPC775_TABLE,
+    WPC775,
+    33,
+    [
         'Ć', 'ü', 'é', 'ā', 'ä', 'ģ', 'å', 'ć', 'ł', 'ē', 'Ŗ', 'ŗ', 'ī', 'Ź', 'Ä', 'Å',
         'É', 'æ', 'Æ', 'ō', 'ö', 'Ģ', '¢', 'Ś', 'ś', 'Ö', 'Ü', 'ø', '£', 'Ø', '×', '¤',
         'Ā', 'Ī', 'ó', 'Ż', 'ż', 'ź', '”', '¦', '©', '®', '¬', '½', '¼', 'Ł', '«', '»',
@@ -397,12 +393,14 @@ lazy_static! {
         'Ó', 'ß', 'Ō', 'Ń', 'õ', 'Õ', 'µ', 'ń', 'Ķ', 'ķ', 'Ļ', 'ļ', 'ņ', 'Ē', 'Ņ', '’',
         '-', '±', '“', '¾', '¶', '§', '÷', '„', '°', '∙', '·', '¹', '³', '²', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC855 Page code table
-    static ref PC855_TABLE: HashMap<char, u8> = [
+    PC855_TABLE,
+    PC855,
+    34,
+    [
         'ђ', 'Ђ', 'ѓ', 'Ѓ', 'ё', 'Ё', 'є', 'Є', 'ѕ', 'Ѕ', 'і', 'І', 'ї', 'Ї', 'ј', 'Ј',
         'љ', 'Љ', 'њ', 'Њ', 'ћ', 'Ћ', 'ќ', 'Ќ', 'ў', 'Ў', 'џ', 'Џ', 'ю', 'Ю', 'ъ', 'Ъ',
         'а', 'А', 'б', 'Б', 'ц', 'Ц', 'д', 'Д', 'е', 'Е', 'ф', 'Ф', 'г', 'Г', '«', '»',
@@ -412,12 +410,14 @@ lazy_static! {
         'Я', 'р', 'Р', 'с', 'С', 'т', 'Т', 'у', 'У', 'ж', 'Ж', 'в', 'В', 'ь', 'Ь', '№',
         '-', 'ы', 'Ы', 'з', 'З', 'ш', 'Ш', 'э', 'Э', 'щ', 'Щ', 'ч', 'Ч', '§', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC861 Page code table
-    static ref PC861_TABLE: HashMap<char, u8> = [
+    PC861_TABLE,
+    PC861,
+    35,
+    [
         'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'Ð', 'ð', 'Þ', 'Ä', 'Å',
         'É', 'æ', 'Æ', 'ô', 'ö', 'þ', 'û', 'Ý', 'ý', 'Ö', 'Ü', 'ø', '£', 'Ø', '₧', 'ƒ',
         'á', 'í', 'ó', 'ú', 'Á', 'Í', 'Ó', 'Ú', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
@@ -427,12 +427,14 @@ lazy_static! {
         'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
         '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC862 Page code table
-    static ref PC862_TABLE: HashMap<char, u8> = [
+    PC862_TABLE,
+    PC862,
+    36,
+    [
         'א', 'ב', 'ג', 'ד', 'ה', 'ו', 'ז', 'ח', 'ט', 'י', 'ך', 'כ', 'ל', 'ם', 'מ', 'ן',
         'נ', 'ס', 'ע', 'ף', 'פ', 'ץ', 'צ', 'ק', 'ר', 'ש', 'ת', '¢', '£', '¥', '₧', 'ƒ',
         'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
@@ -442,14 +444,15 @@ lazy_static! {
         'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
         '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC869 Page code table
-    /// Uses '\0' as placeholder for empty spots
-    static ref PC869_TABLE: HashMap<char, u8> = [
-        'Ά', '€', '·', '¬', '¦', '‘', '’', 'Έ', '―', 'Ή',
+    PC869_TABLE,
+    PC869,
+    38,
+    [
+        '\0', '\0', '\0', '\0', '\0', '\0', 'Ά', '€', '·', '¬', '¦', '‘', '’', 'Έ', '―', 'Ή',
         'Ί', 'Ϊ', 'Ό', '\0', '\0', 'Ύ', 'Ϋ', '©', 'Ώ', '²', '³', 'ά', '£', 'έ', 'ή', 'ί',
         'ϊ', 'ΐ', 'ό', 'ύ', 'Α', 'Β', 'Γ', 'Δ', 'Ε', 'Ζ', 'Η', '½', 'Θ', 'Ι', '«', '»',
         '░', '▒', '▓', '│', '┤', 'Κ', 'Λ', 'Μ', 'Ν', '╣', '║', '╗', '╝', 'Ξ', 'Ο', '┐',
@@ -458,13 +461,14 @@ lazy_static! {
         'ζ', 'η', 'θ', 'ι', 'κ', 'λ', 'μ', 'ν', 'ξ', 'ο', 'π', 'ρ', 'σ', 'ς', 'τ', '΄',
         '-', '±', 'υ', 'φ', 'χ', '§', 'ψ', '΅', '°', '¨', 'ω', 'ϋ', 'ΰ', 'ώ', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .filter(|(_, c)| *c != '\0')
-    .map(|(i, c)| (c, (i + 0x86) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC1118 Page code table
-    static ref PC1118_TABLE: HashMap<char, u8> = [
+    PC1118_TABLE,
+    PC1118,
+    42,
+    [
         'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
         'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
         'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
@@ -474,12 +478,14 @@ lazy_static! {
         'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
         '≡', '±', '≥', '≤', '„', '“', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC1119 Page code table
-    static ref PC1119_TABLE: HashMap<char, u8> = [
+    PC1119_TABLE,
+    PC1119,
+    43,
+    [
         'А', 'Б', 'В', 'Г', 'Д', 'Е', 'Ж', 'З', 'И', 'Й', 'К', 'Л', 'М', 'Н', 'О', 'П',
         'Р', 'С', 'Т', 'У', 'Ф', 'Х', 'Ц', 'Ч', 'Ш', 'Щ', 'Ъ', 'Ы', 'Ь', 'Э', 'Ю', 'Я',
         'а', 'б', 'в', 'г', 'д', 'е', 'ж', 'з', 'и', 'й', 'к', 'л', 'м', 'н', 'о', 'п',
@@ -489,12 +495,14 @@ lazy_static! {
         'р', 'с', 'т', 'у', 'ф', 'х', 'ц', 'ч', 'ш', 'щ', 'ъ', 'ы', 'ь', 'э', 'ю', 'я',
         'Ё', 'ё', '≥', '≤', '„', '“', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// PC1125 Page code table
-    static ref PC1125_TABLE: HashMap<char, u8> = [
+    PC1125_TABLE,
+    PC1125,
+    44,
+    [
         'А', 'Б', 'В', 'Г', 'Д', 'Е', 'Ж', 'З', 'И', 'Й', 'К', 'Л', 'М', 'Н', 'О', 'П',
         'Р', 'С', 'Т', 'У', 'Ф', 'Х', 'Ц', 'Ч', 'Ш', 'Щ', 'Ъ', 'Ы', 'Ь', 'Э', 'Ю', 'Я',
         'а', 'б', 'в', 'г', 'д', 'е', 'ж', 'з', 'и', 'й', 'к', 'л', 'м', 'н', 'о', 'п',
@@ -504,13 +512,14 @@ lazy_static! {
         'р', 'с', 'т', 'у', 'ф', 'х', 'ц', 'ч', 'ш', 'щ', 'ъ', 'ы', 'ь', 'э', 'ю', 'я',
         'Ё', 'ё', 'Ґ', 'ґ', 'Є', 'є', 'І', 'і', 'Ї', 'ї', '÷', '±', '№', '¤', '■', '\u{00A0}',
     ]
-    .into_iter().enumerate()
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// WPC1250 Page code table
-    /// Uses '\0' as placeholder for empty spots
-    static ref WPC1250_TABLE: HashMap<char, u8> = [
+    WPC1250_TABLE,
+    WPC1250,
+    45,
+    [
         '€', '\0', '‚', '\0', '„', '…', '†', '‡', '\0', '‰', 'Š', '‹', 'Ś', 'Ť', 'Ž', 'Ź',
         '\0', '‘', '’', '“', '”', '•', '–', '—', '\0', '™', 'š', '›', 'ś', 'ť', 'ž', 'ź',
         '\u{00A0}', 'ˇ', '˘', 'Ł', '¤', 'Ą', '¦', '§', '¨', '©', 'Ş', '«', '¬', '-', '®', 'Ż',
@@ -520,14 +529,14 @@ lazy_static! {
         'ŕ', 'á', 'â', 'ă', 'ä', 'ĺ', 'ć', 'ç', 'č', 'é', 'ę', 'ë', 'ě', 'í', 'î', 'ď',
         'đ', 'ń', 'ň', 'ó', 'ô', 'ő', 'ö', '÷', 'ř', 'ů', 'ú', 'ű', 'ü', 'ý', 'ţ', '˙',
     ]
-    .into_iter().enumerate()
-    .filter(|(_, c)| *c != '\0')
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// WPC1251 Page code table
-    /// Uses '\0' as placeholder for empty spots
-    static ref WPC1251_TABLE: HashMap<char, u8> = [
+    WPC1251_TABLE,
+    WPC1251,
+    46,
+    [
         'Ђ', 'Ѓ', '‚', 'ѓ', '„', '…', '†', '‡', '€', '‰', 'Љ', '‹', 'Њ', 'Ќ', 'Ћ', 'Џ',
         'ђ', '‘', '’', '“', '”', '•', '–', '—', '\0', '™', 'љ', '›', 'њ', 'ќ', 'ћ', 'џ',
         '\u{00A0}', 'Ў', 'ў', 'Ј', '¤', 'Ґ', '¦', '§', 'Ё', '©', 'Є', '«', '¬', '-', '®', 'Ї',
@@ -537,14 +546,14 @@ lazy_static! {
         'а', 'б', 'в', 'г', 'д', 'е', 'ж', 'з', 'и', 'й', 'к', 'л', 'м', 'н', 'о', 'п',
         'р', 'с', 'т', 'у', 'ф', 'х', 'ц', 'ч', 'ш', 'щ', 'ъ', 'ы', 'ь', 'э', 'ю', 'я',
     ]
-    .into_iter().enumerate()
-    .filter(|(_, c)| *c != '\0')
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// WPC1253 Page code table
-    /// Uses '\0' as placeholder for empty spots
-    static ref WPC1253_TABLE: HashMap<char, u8> = [
+    WPC1253_TABLE,
+    WPC1253,
+    47,
+    [
         '€', '\0', '‚', 'ƒ', '„', '…', '†', '‡', '\0', '‰', '\0', '‹', '\0', '\0', '\0', '\0',
         '\0', '‘', '’', '“', '”', '•', '–', '—', '\0', '™', '\0', '›', '\0', '\0', '\0', '\0',
         '\u{00A0}', '΅', 'Ά', '£', '¤', '¥', '¦', '§', '¨', '©', '\0', '«', '¬', '-', '®', '―',
@@ -554,14 +563,14 @@ lazy_static! {
         'ΰ', 'α', 'β', 'γ', 'δ', 'ε', 'ζ', 'η', 'θ', 'ι', 'κ', 'λ', 'μ', 'ν', 'ξ', 'ο',
         'π', 'ρ', 'ς', 'σ', 'τ', 'υ', 'φ', 'χ', 'ψ', 'ω', 'ϊ', 'ϋ', 'ό', 'ύ', 'ώ', '\0',
     ]
-    .into_iter().enumerate()
-    .filter(|(_, c)| *c != '\0')
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// WPC1254 Page code table
-    /// Uses '\0' as placeholder for empty spots
-    static ref WPC1254_TABLE: HashMap<char, u8> = [
+    WPC1254_TABLE,
+    WPC1254,
+    48,
+    [
         '€', '\0', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\0', '\0', '\0',
         '\0', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\0', '\0', 'Ÿ',
         '\u{00A0}', '¡', '¢', '£', '¤', '¥', '¦', '§', '¨', '©', 'ª', '«', '¬', '-', '®', '¯',
@@ -571,14 +580,14 @@ lazy_static! {
         'à', 'á', 'â', 'ã', 'ä', 'å', 'æ', 'ç', 'è', 'é', 'ê', 'ë', 'ì', 'í', 'î', 'ï',
         'ğ', 'ñ', 'ò', 'ó', 'ô', 'õ', 'ö', '÷', 'ø', 'ù', 'ú', 'û', 'ü', 'ı', 'ş', 'ÿ',
     ]
-    .into_iter().enumerate()
-    .filter(|(_, c)| *c != '\0')
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// WPC1257 Page code table
-    /// Uses '\0' as placeholder for empty spots
-    static ref WPC1257_TABLE: HashMap<char, u8> = [
+    WPC1257_TABLE,
+    WPC1257,
+    51,
+    [
         '€', '\0', '‚', '\0', '„', '…', '†', '‡', '\0', '‰', '\0', '‹', '\0', '¨', 'ˇ', '¸',
         '\0', '‘', '’', '“', '”', '•', '–', '—', '\0', '™', '\0', '›', '\0', '¯', '˛', '\0',
         '\u{00A0}', '\0', '¢', '£', '¤', '\0', '¦', '§', 'Ø', '©', 'Ŗ', '«', '¬', '-', '®', 'Æ',
@@ -588,14 +597,14 @@ lazy_static! {
         'ą', 'į', 'ā', 'ć', 'ä', 'å', 'ę', 'ē', 'č', 'é', 'ź', 'ė', 'ģ', 'ķ', 'ī', 'ļ',
         'š', 'ń', 'ņ', 'ó', 'ō', 'õ', 'ö', '÷', 'ų', 'ł', 'ś', 'ū', 'ü', 'ż', 'ž', '˙',
     ]
-    .into_iter().enumerate()
-    .filter(|(_, c)| *c != '\0')
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
 
+builtin_code_page!(
     /// KZ1048 Page code table
-    /// Uses '\0' as placeholder for empty spots
-    static ref KZ1048_TABLE: HashMap<char, u8> = [
+    KZ1048_TABLE,
+    KZ1048,
+    53,
+    [
         'Ђ', 'Ѓ', '‚', 'ѓ', '„', '…', '†', '‡', '€', '‰', 'Љ', '‹', 'Њ', 'Қ', 'Һ', 'Џ',
         'ђ', '‘', '’', '“', '”', '•', '–', '—', '\0', '™', 'љ', '›', 'њ', 'қ', 'һ', 'џ',
         '\u{00A0}', 'Ұ', 'ұ', 'Ә', '¤', 'Ө', '¦', '§', 'Ё', '©', 'Ғ', '«', '¬', '-', '®', 'Ү',
@@ -605,8 +614,201 @@ lazy_static! {
         'а', 'б', 'в', 'г', 'д', 'е', 'ж', 'з', 'и', 'й', 'к', 'л', 'м', 'н', 'о', 'п',
         'р', 'с', 'т', 'у', 'ф', 'х', 'ц', 'ч', 'ш', 'щ', 'ъ', 'ы', 'ь', 'э', 'ю', 'я',
     ]
-    .into_iter().enumerate()
-    .filter(|(_, c)| *c != '\0')
-    .map(|(i, c)| (c, (i + 0x80) as u8))
-    .collect();
+);
+
+builtin_code_page!(
+    /// Hiragana Page code table (page 6, same layout as Katakana)
+    HIRAGANA_TABLE,
+    Hiragana,
+    6,
+    [
+        '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+        '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+        '\0', '。', '「', '」', '、', '・', 'を', 'ぁ', 'ぃ', 'ぅ', 'ぇ', 'ぉ', 'ゃ', 'ゅ', 'ょ', 'っ',
+        'ー', 'あ', 'い', 'う', 'え', 'お', 'か', 'き', 'く', 'け', 'こ', 'さ', 'し', 'す', 'せ', 'そ',
+        'た', 'ち', 'つ', 'て', 'と', 'な', 'に', 'ぬ', 'ね', 'の', 'は', 'ひ', 'ふ', 'へ', 'ほ', 'ま',
+        'み', 'む', 'め', 'も', 'や', 'ゆ', 'よ', 'ら', 'り', 'る', 'れ', 'ろ', 'わ', 'ん', '゛', '゜',
+        '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+        '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+    ]
+);
+
+builtin_code_page!(
+    /// PC720 Page code table
+    PC720_TABLE,
+    PC720,
+    32,
+    [
+        '\0', '\0', 'é', 'â', '\0', 'à', '\0', 'ç', 'ê', 'ë', 'è', 'ï', 'î', '\0', '\0', '\0',
+        '\0', '\u{0651}', '\u{0652}', 'ô', '¤', 'ـ', 'û', 'ù', 'ء', 'آ', 'أ', 'ؤ', '£', 'إ', 'ئ', 'ا',
+        'ب', 'ة', 'ت', 'ث', 'ج', 'ح', 'خ', 'د', 'ذ', 'ر', 'ز', 'س', 'ش', 'ص', '«', '»',
+        '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+        '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+        '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+        'ض', 'ط', 'ظ', 'ع', 'غ', 'ف', 'µ', 'ق', 'ك', 'ل', 'م', 'ن', 'ه', 'و', 'ى', 'ي',
+        '≡', '\u{064B}', '\u{064C}', '\u{064D}', '\u{064E}', '\u{064F}', '\u{0650}', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{00A0}',
+    ]
+);
+
+builtin_code_page!(
+    /// PC864 Page code table
+    PC864_TABLE,
+    PC864,
+    37,
+    [
+        '°', '·', '∙', '√', '▒', '─', '│', '┼', '┤', '┬', '├', '┴', '┐', '┌', '└', '┘',
+        'β', '∞', 'φ', '±', '½', '¼', '≈', '«', '»', 'ﻷ', 'ﻸ', '\0', '\0', 'ﻻ', 'ﻼ', '\0',
+        '\u{00A0}', '\u{00AD}', 'ﺂ', '£', '¤', 'ﺄ', '\0', '\0', 'ﺎ', 'ﺏ', 'ﺕ', 'ﺙ', '،', 'ﺝ', 'ﺡ', 'ﺥ',
+        '٠', '١', '٢', '٣', '٤', '٥', '٦', '٧', '٨', '٩', 'ﻑ', '؛', 'ﺱ', 'ﺵ', 'ﺹ', '؟',
+        '¢', 'ﺀ', 'ﺁ', 'ﺃ', 'ﺅ', 'ﻊ', 'ﺋ', 'ﺍ', 'ﺑ', 'ﺓ', 'ﺗ', 'ﺛ', 'ﺟ', 'ﺣ', 'ﺧ', 'ﺩ',
+        'ﺫ', 'ﺭ', 'ﺯ', 'ﺳ', 'ﺷ', 'ﺻ', 'ﺿ', 'ﻁ', 'ﻅ', 'ﻋ', 'ﻏ', '¦', '¬', '÷', '×', 'ﻉ',
+        'ـ', 'ﻓ', 'ﻗ', 'ﻛ', 'ﻟ', 'ﻣ', 'ﻧ', 'ﻫ', 'ﻭ', 'ﻯ', 'ﻳ', 'ﺽ', 'ﻌ', 'ﻎ', 'ﻍ', 'ﻡ',
+        'ﹽ', '\u{0651}', 'ﻥ', 'ﻩ', 'ﻬ', 'ﻰ', 'ﻲ', 'ﻐ', 'ﻕ', 'ﻵ', 'ﻶ', 'ﻝ', 'ﻙ', 'ﻱ', '■', '\0',
+    ]
+);
+
+builtin_code_page!(
+    /// PC1098 Page code table (IBM-1098)
+    PC1098_TABLE,
+    PC1098,
+    41,
+    [
+        '\0', '\0', '،', '؛', '؟', '\u{064B}', 'ﺁ', 'ﺂ', '\0', 'ﺍ', 'ﺎ', '\0', 'ﺀ', 'ﺃ', 'ﺄ', '\0',
+        'ﺅ', 'ﺋ', 'ﺏ', 'ﺑ', 'ﭖ', 'ﭘ', 'ﺕ', 'ﺗ', 'ﺙ', 'ﺛ', 'ﺝ', 'ﺟ', 'ﭺ', 'ﭼ', '×', 'ﺡ',
+        'ﺣ', 'ﺥ', 'ﺧ', 'ﺩ', 'ﺫ', 'ﺭ', 'ﺯ', 'ﮊ', 'ﺱ', 'ﺳ', 'ﺵ', 'ﺷ', 'ﺹ', 'ﺻ', '«', '»',
+        '░', '▒', '▓', '│', '┤', 'ﺽ', 'ﺿ', 'ﻁ', 'ﻃ', '╣', '║', '╗', '╝', '¤', 'ﻅ', '┐',
+        '└', '┴', '┬', '├', '─', '┼', 'ﻇ', 'ﻉ', '╚', '╔', '╩', '╦', '╠', '═', '╬', '\0',
+        'ﻊ', 'ﻋ', 'ﻌ', 'ﻍ', 'ﻎ', 'ﻏ', 'ﻐ', 'ﻑ', 'ﻓ', '┘', '┌', '█', '▄', 'ﻕ', 'ﻗ', '▀',
+        'ﮎ', 'ﻛ', 'ﮒ', 'ﮔ', 'ﻝ', 'ﻟ', 'ﻡ', 'ﻣ', 'ﻥ', 'ﻧ', 'ﻭ', 'ﻩ', 'ﻫ', 'ﻬ', 'ﮤ', 'ﯼ',
+        '\u{00AD}', 'ﯽ', 'ﯾ', 'ـ', '۰', '۱', '۲', '۳', '۴', '۵', '۶', '۷', '۸', '۹', '■', '\u{00A0}',
+    ]
+);
+
+builtin_code_page!(
+    /// WPC1255 Page code table
+    WPC1255_TABLE,
+    WPC1255,
+    49,
+    [
+        '€', '\0', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', '\0', '‹', '\0', '\0', '\0', '\0',
+        '\0', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', '\0', '›', '\0', '\0', '\0', '\0',
+        '\u{00A0}', '¡', '¢', '£', '₪', '¥', '¦', '§', '¨', '©', '×', '«', '¬', '\u{00AD}', '®', '¯',
+        '°', '±', '²', '³', '´', 'µ', '¶', '·', '¸', '¹', '÷', '»', '¼', '½', '¾', '¿',
+        '\u{05B0}', '\u{05B1}', '\u{05B2}', '\u{05B3}', '\u{05B4}', '\u{05B5}', '\u{05B6}', '\u{05B7}', '\u{05B8}', '\u{05B9}', '\0', '\u{05BB}', '\u{05BC}', '\u{05BD}', '־', '\u{05BF}',
+        '׀', '\u{05C1}', '\u{05C2}', '׃', 'װ', 'ױ', 'ײ', '׳', '״', '\0', '\0', '\0', '\0', '\0', '\0', '\0',
+        'א', 'ב', 'ג', 'ד', 'ה', 'ו', 'ז', 'ח', 'ט', 'י', 'ך', 'כ', 'ל', 'ם', 'מ', 'ן',
+        'נ', 'ס', 'ע', 'ף', 'פ', 'ץ', 'צ', 'ק', 'ר', 'ש', 'ת', '\0', '\0', '\u{200E}', '\u{200F}', '\0',
+    ]
+);
+
+builtin_code_page!(
+    /// WPC1256 Page code table
+    WPC1256_TABLE,
+    WPC1256,
+    50,
+    [
+        '€', 'پ', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'ٹ', '‹', 'Œ', 'چ', 'ژ', 'ڈ',
+        'گ', '‘', '’', '“', '”', '•', '–', '—', 'ک', '™', 'ڑ', '›', 'œ', '\u{200C}', '\u{200D}', 'ں',
+        '\u{00A0}', '،', '¢', '£', '¤', '¥', '¦', '§', '¨', '©', 'ھ', '«', '¬', '\u{00AD}', '®', '¯',
+        '°', '±', '²', '³', '´', 'µ', '¶', '·', '¸', '¹', '؛', '»', '¼', '½', '¾', '؟',
+        'ہ', 'ء', 'آ', 'أ', 'ؤ', 'إ', 'ئ', 'ا', 'ب', 'ة', 'ت', 'ث', 'ج', 'ح', 'خ', 'د',
+        'ذ', 'ر', 'ز', 'س', 'ش', 'ص', 'ض', '×', 'ط', 'ظ', 'ع', 'غ', 'ـ', 'ف', 'ق', 'ك',
+        'à', 'ل', 'â', 'م', 'ن', 'ه', 'و', 'ç', 'è', 'é', 'ê', 'ë', 'ى', 'ي', 'î', 'ï',
+        '\u{064B}', '\u{064C}', '\u{064D}', '\u{064E}', 'ô', '\u{064F}', '\u{0650}', '÷', '\u{0651}', 'ù', '\u{0652}', 'û', 'ü', '\u{200E}', '\u{200F}', 'ے',
+    ]
+);
+
+builtin_code_page!(
+    /// WPC1258 Page code table
+    WPC1258_TABLE,
+    WPC1258,
+    52,
+    [
+        '€', '\0', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', '\0', '‹', 'Œ', '\0', '\0', '\0',
+        '\0', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', '\0', '›', 'œ', '\0', '\0', 'Ÿ',
+        '\u{00A0}', '¡', '¢', '£', '¤', '¥', '¦', '§', '¨', '©', 'ª', '«', '¬', '\u{00AD}', '®', '¯',
+        '°', '±', '²', '³', '´', 'µ', '¶', '·', '¸', '¹', 'º', '»', '¼', '½', '¾', '¿',
+        'À', 'Á', 'Â', 'Ă', 'Ä', 'Å', 'Æ', 'Ç', 'È', 'É', 'Ê', 'Ë', '\u{0300}', 'Í', 'Î', 'Ï',
+        'Đ', 'Ñ', '\u{0309}', 'Ó', 'Ô', 'Ơ', 'Ö', '×', 'Ø', 'Ù', 'Ú', 'Û', 'Ü', 'Ư', '\u{0303}', 'ß',
+        'à', 'á', 'â', 'ă', 'ä', 'å', 'æ', 'ç', 'è', 'é', 'ê', 'ë', '\u{0301}', 'í', 'î', 'ï',
+        'đ', 'ñ', '\u{0323}', 'ó', 'ô', 'ơ', 'ö', '÷', 'ø', 'ù', 'ú', 'û', 'ü', 'ư', '₫', 'ÿ',
+    ]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check that each mapped byte is decoded to its character and encoded back
+    fn assert_round_trip(page_code: PageCode, len: usize) {
+        let table = code_page(page_code);
+        assert_eq!(table.len(), len, "{page_code}");
+
+        for (c, byte) in table.chars() {
+            assert!(byte >= 0x80, "{page_code}: {c:?}");
+            assert_eq!(table.char(byte), Some(c), "{page_code}: {byte:#04x}");
+        }
+    }
+
+    #[test]
+    fn test_hiragana() {
+        assert_round_trip(PageCode::Hiragana, 63);
+        assert_eq!(code_page(PageCode::Hiragana).byte('あ'), Some(0xB1));
+    }
+
+    #[test]
+    fn test_pc720() {
+        assert_round_trip(PageCode::PC720, 120);
+        assert_eq!(code_page(PageCode::PC720).byte('ب'), Some(0xA0));
+    }
+
+    #[test]
+    fn test_pc864() {
+        assert_round_trip(PageCode::PC864, 122);
+        assert_eq!(code_page(PageCode::PC864).byte('٣'), Some(0xB3));
+    }
+
+    #[test]
+    fn test_pc874() {
+        assert_round_trip(PageCode::PC874, 97);
+        assert_eq!(code_page(PageCode::PC874).byte('ก'), Some(0xA1));
+        assert_eq!(code_page(PageCode::PC874).byte('\u{0E48}'), Some(0xE8));
+    }
+
+    #[test]
+    fn test_pc1098() {
+        assert_round_trip(PageCode::PC1098, 122);
+        assert_eq!(code_page(PageCode::PC1098).byte('۴'), Some(0xF8));
+    }
+
+    #[test]
+    fn test_wpc1255() {
+        assert_round_trip(PageCode::WPC1255, 105);
+        assert_eq!(code_page(PageCode::WPC1255).byte('₪'), Some(0xA4));
+    }
+
+    #[test]
+    fn test_wpc1256() {
+        assert_round_trip(PageCode::WPC1256, 128);
+        assert_eq!(code_page(PageCode::WPC1256).byte('ع'), Some(0xDA));
+    }
+
+    #[test]
+    fn test_wpc1258() {
+        assert_round_trip(PageCode::WPC1258, 119);
+        assert_eq!(code_page(PageCode::WPC1258).byte('₫'), Some(0xFE));
+    }
+
+    #[test]
+    fn test_all_page_codes() {
+        for page_code in PageCode::all() {
+            let table = code_page(page_code);
+            assert_eq!(table.page_code(), page_code);
+            assert_eq!(table.number(), u8::from(page_code));
+            for (c, byte) in table.chars() {
+                assert_eq!(table.char(byte), Some(c), "{page_code}: {byte:#04x}");
+                assert_eq!(table.byte(c), Some(byte), "{page_code}: {c:?}");
+            }
+        }
+    }
 }
@@ -0,0 +1,561 @@
+//! Printer capability profiles
+
+use super::{Font, PageCode};
+use crate::errors::{PrinterError, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+#[cfg(feature = "serde")]
+use std::{fs, path::Path};
+
+/// Page codes commonly found on Star printers (ESC/POS mode) and cheap clones
+const COMMON_PAGE_CODES: [PageCode; 10] = [
+    PageCode::PC437,
+    PageCode::Katakana,
+    PageCode::PC850,
+    PageCode::PC860,
+    PageCode::PC863,
+    PageCode::PC865,
+    PageCode::WPC1252,
+    PageCode::PC866,
+    PageCode::PC852,
+    PageCode::PC858,
+];
+
+/// Command family a printer may support
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum Capability {
+    /// Page mode (ESC L, ESC W, ESC T...)
+    PageMode,
+    /// Graphics (GS ( L, GS 8 L)
+    Graphics,
+    /// Raster bit image (GS v 0)
+    BitImage,
+    /// 1D barcodes (GS k)
+    Barcodes,
+    /// QR code (GS ( k, cn = 49)
+    #[cfg_attr(feature = "serde", serde(rename = "qr_code"))]
+    QRCode,
+    /// PDF417 (GS ( k, cn = 48)
+    Pdf417,
+    /// MaxiCode (GS ( k, cn = 50)
+    MaxiCode,
+    /// 2D GS1 DataBar (GS ( k, cn = 51)
+    #[cfg_attr(feature = "serde", serde(rename = "gs1_databar_2d"))]
+    GS1DataBar2D,
+    /// Aztec code (GS ( k, cn = 53)
+    Aztec,
+    /// DataMatrix (GS ( k, cn = 54)
+    DataMatrix,
+    /// Full cut (GS V)
+    FullCut,
+    /// Partial cut (GS V)
+    PartialCut,
+    /// Cash drawer kick-out (ESC p)
+    CashDrawer,
+    /// Real-time status (DLE EOT)
+    RealTimeStatus,
+    /// Kanji / CJK double-byte characters (FS &, FS .)
+    Kanji,
+    /// UTF-8 encoding and font priorities (FS ( C)
+    Unicode,
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Capability::PageMode => write!(f, "page mode"),
+            Capability::Graphics => write!(f, "graphics"),
+            Capability::BitImage => write!(f, "bit image"),
+            Capability::Barcodes => write!(f, "barcodes"),
+            Capability::QRCode => write!(f, "QR code"),
+            Capability::Pdf417 => write!(f, "PDF417"),
+            Capability::MaxiCode => write!(f, "MaxiCode"),
+            Capability::GS1DataBar2D => write!(f, "2D GS1 DataBar"),
+            Capability::Aztec => write!(f, "Aztec code"),
+            Capability::DataMatrix => write!(f, "DataMatrix"),
+            Capability::FullCut => write!(f, "full cut"),
+            Capability::PartialCut => write!(f, "partial cut"),
+            Capability::CashDrawer => write!(f, "cash drawer"),
+            Capability::RealTimeStatus => write!(f, "real-time status"),
+            Capability::Kanji => write!(f, "Kanji mode"),
+            Capability::Unicode => write!(f, "Unicode mode"),
+        }
+    }
+}
+
+/// Behavior of the printer when an instruction is not supported by its profile
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum CapabilityPolicy {
+    /// Return a `PrinterError::Unsupported` error
+    #[default]
+    Strict,
+    /// Use a supported fallback (font A, full cut, raster QR code...) and return an error if there is none
+    Degrade,
+}
+
+impl fmt::Display for CapabilityPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CapabilityPolicy::Strict => write!(f, "Strict"),
+            CapabilityPolicy::Degrade => write!(f, "Degrade"),
+        }
+    }
+}
+
+/// Font available on a printer
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FontProfile {
+    /// Font
+    pub font: Font,
+    /// Character width in dots
+    pub width: u8,
+    /// Character height in dots
+    pub height: u8,
+    /// Number of characters per line
+    pub columns: u8,
+}
+
+/// Page code available on a printer with its ESC t number
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PageCodeProfile {
+    /// Page code
+    pub page_code: PageCode,
+    /// ESC t number
+    pub number: u8,
+}
+
+/// Built-in printer models
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PrinterModel {
+    EpsonTmT20,
+    EpsonTmT88,
+    EpsonTmM30,
+    StarTsp100,
+    XprinterXp58,
+    XprinterXp80,
+    GoojprtPt210,
+}
+
+impl fmt::Display for PrinterModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrinterModel::EpsonTmT20 => write!(f, "Epson TM-T20"),
+            PrinterModel::EpsonTmT88 => write!(f, "Epson TM-T88"),
+            PrinterModel::EpsonTmM30 => write!(f, "Epson TM-m30"),
+            PrinterModel::StarTsp100 => write!(f, "Star TSP100"),
+            PrinterModel::XprinterXp58 => write!(f, "Xprinter XP-58"),
+            PrinterModel::XprinterXp80 => write!(f, "Xprinter XP-80"),
+            PrinterModel::GoojprtPt210 => write!(f, "GOOJPRT PT-210"),
+        }
+    }
+}
+
+impl PrinterModel {
+    /// List of the built-in models
+    pub fn all() -> Vec<Self> {
+        vec![
+            PrinterModel::EpsonTmT20,
+            PrinterModel::EpsonTmT88,
+            PrinterModel::EpsonTmM30,
+            PrinterModel::StarTsp100,
+            PrinterModel::XprinterXp58,
+            PrinterModel::XprinterXp80,
+            PrinterModel::GoojprtPt210,
+        ]
+    }
+}
+
+/// Printer profile
+///
+/// Describes what a printer model supports: paper width, fonts, page codes and command families.
+///
+/// # Examples
+///
+/// ```rust
+/// use escpos::utils::*;
+///
+/// let profile = PrinterProfile::from(PrinterModel::EpsonTmT88);
+/// assert_eq!(profile.dots_per_line(), 512);
+/// assert_eq!(profile.columns(Font::A), Some(42));
+/// assert!(profile.has_capability(Capability::PageMode));
+///
+/// let custom = PrinterProfile::new("My clone", 384, 203)
+///     .with_font(Font::A, 12, 24, 32)
+///     .with_page_code(PageCode::PC866, 17)
+///     .with_capability(Capability::QRCode);
+/// assert_eq!(custom.page_code_number(PageCode::PC866), Some(17));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PrinterProfile {
+    name: String,
+    dots_per_line: u16,
+    dpi: u16,
+    #[cfg_attr(feature = "serde", serde(default))]
+    fonts: Vec<FontProfile>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    page_codes: Vec<PageCodeProfile>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    capabilities: Vec<Capability>,
+}
+
+impl From<PrinterModel> for PrinterProfile {
+    fn from(model: PrinterModel) -> Self {
+        let name = model.to_string();
+
+        match model {
+            PrinterModel::EpsonTmT20 => Self::new(&name, 576, 203)
+                .with_font(Font::A, 12, 24, 48)
+                .with_font(Font::B, 9, 17, 64)
+                .with_page_codes(&PageCode::all())
+                .with_capabilities(&[
+                    Capability::PageMode,
+                    Capability::Graphics,
+                    Capability::BitImage,
+                    Capability::Barcodes,
+                    Capability::QRCode,
+                    Capability::Pdf417,
+                    Capability::MaxiCode,
+                    Capability::GS1DataBar2D,
+                    Capability::FullCut,
+                    Capability::PartialCut,
+                    Capability::CashDrawer,
+                    Capability::RealTimeStatus,
+                ]),
+            PrinterModel::EpsonTmT88 | PrinterModel::EpsonTmM30 => {
+                let profile =
+                    match model {
+                        PrinterModel::EpsonTmT88 => Self::new(&name, 512, 180)
+                            .with_font(Font::A, 12, 24, 42)
+                            .with_font(Font::B, 9, 17, 56),
+                        _ => Self::new(&name, 576, 203)
+                            .with_font(Font::A, 12, 24, 48)
+                            .with_font(Font::B, 9, 17, 64),
+                    };
+
+                profile.with_page_codes(&PageCode::all()).with_capabilities(&[
+                    Capability::PageMode,
+                    Capability::Graphics,
+                    Capability::BitImage,
+                    Capability::Barcodes,
+                    Capability::QRCode,
+                    Capability::Pdf417,
+                    Capability::MaxiCode,
+                    Capability::GS1DataBar2D,
+                    Capability::Aztec,
+                    Capability::DataMatrix,
+                    Capability::FullCut,
+                    Capability::PartialCut,
+                    Capability::CashDrawer,
+                    Capability::RealTimeStatus,
+                    Capability::Unicode,
+                ])
+            }
+            PrinterModel::StarTsp100 => Self::new(&name, 576, 203)
+                .with_font(Font::A, 12, 24, 48)
+                .with_font(Font::B, 9, 24, 64)
+                .with_page_codes(&COMMON_PAGE_CODES)
+                .with_capabilities(&[
+                    Capability::BitImage,
+                    Capability::Barcodes,
+                    Capability::QRCode,
+                    Capability::Pdf417,
+                    Capability::FullCut,
+                    Capability::PartialCut,
+                    Capability::CashDrawer,
+                    Capability::RealTimeStatus,
+                ]),
+            PrinterModel::XprinterXp80 => Self::new(&name, 576, 203)
+                .with_font(Font::A, 12, 24, 48)
+                .with_font(Font::B, 9, 17, 64)
+                .with_page_codes(&COMMON_PAGE_CODES)
+                .with_capabilities(&[
+                    Capability::BitImage,
+                    Capability::Barcodes,
+                    Capability::QRCode,
+                    Capability::FullCut,
+                    Capability::PartialCut,
+                    Capability::CashDrawer,
+                    Capability::RealTimeStatus,
+                    Capability::Kanji,
+                ]),
+            PrinterModel::XprinterXp58 | PrinterModel::GoojprtPt210 => Self::new(&name, 384, 203)
+                .with_font(Font::A, 12, 24, 32)
+                .with_font(Font::B, 9, 17, 42)
+                .with_page_codes(&COMMON_PAGE_CODES)
+                .with_capabilities(&[
+                    Capability::BitImage,
+                    Capability::Barcodes,
+                    Capability::QRCode,
+                    Capability::RealTimeStatus,
+                    Capability::Kanji,
+                ]),
+        }
+    }
+}
+
+impl PrinterProfile {
+    /// Create a new `PrinterProfile` without fonts, page codes and capabilities
+    pub fn new(name: &str, dots_per_line: u16, dpi: u16) -> Self {
+        Self {
+            name: name.to_string(),
+            dots_per_line,
+            dpi,
+            fonts: vec![],
+            page_codes: vec![],
+            capabilities: vec![],
+        }
+    }
+
+    /// Add (or replace) a font with its character size in dots and its number of characters per line
+    pub fn with_font(mut self, font: Font, width: u8, height: u8, columns: u8) -> Self {
+        self.fonts.retain(|profile| profile.font != font);
+        self.fonts.push(FontProfile {
+            font,
+            width,
+            height,
+            columns,
+        });
+        self
+    }
+
+    /// Add (or replace) a page code with its ESC t number
+    pub fn with_page_code(mut self, page_code: PageCode, number: u8) -> Self {
+        self.page_codes.retain(|profile| profile.page_code != page_code);
+        self.page_codes.push(PageCodeProfile { page_code, number });
+        self
+    }
+
+    /// Add page codes with their default ESC t number
+    fn with_page_codes(self, page_codes: &[PageCode]) -> Self {
+        page_codes.iter().fold(self, |profile, &page_code| {
+            profile.with_page_code(page_code, page_code.into())
+        })
+    }
+
+    /// Add a capability
+    pub fn with_capability(mut self, capability: Capability) -> Self {
+        if !self.capabilities.contains(&capability) {
+            self.capabilities.push(capability);
+        }
+        self
+    }
+
+    /// Add capabilities
+    fn with_capabilities(self, capabilities: &[Capability]) -> Self {
+        capabilities
+            .iter()
+            .fold(self, |profile, &capability| profile.with_capability(capability))
+    }
+
+    /// Get name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get number of dots per line
+    pub fn dots_per_line(&self) -> u16 {
+        self.dots_per_line
+    }
+
+    /// Get resolution in dots per inch
+    pub fn dpi(&self) -> u16 {
+        self.dpi
+    }
+
+    /// Get fonts
+    pub fn fonts(&self) -> &[FontProfile] {
+        &self.fonts
+    }
+
+    /// Get page codes
+    pub fn page_codes(&self) -> &[PageCodeProfile] {
+        &self.page_codes
+    }
+
+    /// Get capabilities
+    pub fn capabilities(&self) -> &[Capability] {
+        &self.capabilities
+    }
+
+    /// Get a font
+    pub fn font(&self, font: Font) -> Option<&FontProfile> {
+        self.fonts.iter().find(|profile| profile.font == font)
+    }
+
+    /// Get the number of characters per line for a font
+    pub fn columns(&self, font: Font) -> Option<u8> {
+        self.font(font).map(|profile| profile.columns)
+    }
+
+    /// Get the ESC t number of a page code
+    pub fn page_code_number(&self, page_code: PageCode) -> Option<u8> {
+        self.page_codes
+            .iter()
+            .find(|profile| profile.page_code == page_code)
+            .map(|profile| profile.number)
+    }
+
+    /// Check if the printer supports a page code
+    pub fn has_page_code(&self, page_code: PageCode) -> bool {
+        self.page_code_number(page_code).is_some()
+    }
+
+    /// Check if the printer supports a command family
+    pub fn has_capability(&self, capability: Capability) -> bool {
+        self.capabilities.contains(&capability)
+    }
+
+    /// Check that the profile is consistent
+    pub fn validate(&self) -> Result<()> {
+        if self.dots_per_line == 0 || self.dpi == 0 {
+            return Err(PrinterError::Input(format!(
+                "invalid profile {}: dots per line and dpi must be greater than 0",
+                self.name
+            )));
+        }
+        if let Some(font) = self.fonts.iter().find(|font| font.width == 0 || font.columns == 0) {
+            return Err(PrinterError::Input(format!(
+                "invalid profile {}: invalid size for {}",
+                self.name, font.font
+            )));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    /// Load a profile from JSON
+    pub fn from_json(data: &str) -> Result<Self> {
+        let profile = serde_json::from_str::<Self>(data)
+            .map_err(|e| PrinterError::Input(format!("invalid JSON profile: {e}")))?;
+        profile.validate()?;
+
+        Ok(profile)
+    }
+
+    #[cfg(feature = "serde")]
+    /// Load a profile from TOML
+    pub fn from_toml(data: &str) -> Result<Self> {
+        let profile =
+            toml::from_str::<Self>(data).map_err(|e| PrinterError::Input(format!("invalid TOML profile: {e}")))?;
+        profile.validate()?;
+
+        Ok(profile)
+    }
+
+    #[cfg(feature = "serde")]
+    /// Load a profile from a JSON (`.json`) or TOML (`.toml`) file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let data = fs::read_to_string(path)?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Self::from_json(&data),
+            Some("toml") => Self::from_toml(&data),
+            _ => Err(PrinterError::Input(format!(
+                "invalid profile file extension (json or toml expected): {}",
+                path.display()
+            ))),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    /// Export the profile to JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| PrinterError::Input(format!("invalid profile: {e}")))
+    }
+
+    #[cfg(feature = "serde")]
+    /// Export the profile to TOML
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string_pretty(self).map_err(|e| PrinterError::Input(format!("invalid profile: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_built_in_profiles() {
+        for model in PrinterModel::all() {
+            let profile = PrinterProfile::from(model);
+            assert!(profile.validate().is_ok());
+            assert_eq!(profile.name(), model.to_string());
+            assert!(profile.columns(Font::A).is_some());
+            assert_eq!(profile.page_code_number(PageCode::PC437), Some(0));
+        }
+
+        let profile = PrinterProfile::from(PrinterModel::XprinterXp58);
+        assert_eq!(profile.dots_per_line(), 384);
+        assert_eq!(profile.columns(Font::B), Some(42));
+        assert_eq!(profile.columns(Font::C), None);
+        assert!(!profile.has_page_code(PageCode::PC720));
+        assert!(!profile.has_capability(Capability::PartialCut));
+
+        let profile = PrinterProfile::from(PrinterModel::EpsonTmT88);
+        assert_eq!(profile.page_code_number(PageCode::KZ1048), Some(53));
+        assert!(profile.has_capability(Capability::MaxiCode));
+    }
+
+    #[test]
+    fn test_custom_profile() {
+        let profile = PrinterProfile::new("Clone", 384, 203)
+            .with_font(Font::A, 12, 24, 32)
+            .with_font(Font::A, 12, 24, 30)
+            .with_page_code(PageCode::WPC1252, 16)
+            .with_page_code(PageCode::WPC1252, 71)
+            .with_capability(Capability::QRCode)
+            .with_capability(Capability::QRCode);
+
+        assert_eq!(profile.fonts().len(), 1);
+        assert_eq!(profile.columns(Font::A), Some(30));
+        assert_eq!(profile.page_code_number(PageCode::WPC1252), Some(71));
+        assert_eq!(profile.capabilities(), &[Capability::QRCode]);
+
+        assert!(PrinterProfile::new("Invalid", 0, 203).validate().is_err());
+        assert!(PrinterProfile::new("Invalid", 384, 203)
+            .with_font(Font::B, 0, 17, 42)
+            .validate()
+            .is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_profile_from_toml() {
+        let profile = PrinterProfile::from_toml(
+            r#"
+            name = "My clone"
+            dots_per_line = 384
+            dpi = 203
+            fonts = [{ font = "A", width = 12, height = 24, columns = 32 }]
+            page_codes = [{ page_code = "PC866", number = 17 }, { page_code = "WPC1252", number = 71 }]
+            capabilities = ["qr_code", "partial_cut"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(profile.name(), "My clone");
+        assert_eq!(profile.columns(Font::A), Some(32));
+        assert_eq!(profile.page_code_number(PageCode::WPC1252), Some(71));
+        assert!(profile.has_capability(Capability::PartialCut));
+        assert!(!profile.has_capability(Capability::PageMode));
+
+        assert!(PrinterProfile::from_toml("name = \"Missing width\"").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_profile_json_round_trip() {
+        let profile = PrinterProfile::from(PrinterModel::StarTsp100);
+        let json = profile.to_json().unwrap();
+
+        assert_eq!(PrinterProfile::from_json(&json).unwrap(), profile);
+        assert_eq!(PrinterProfile::from_toml(&profile.to_toml().unwrap()).unwrap(), profile);
+        assert!(PrinterProfile::from_json("{}").is_err());
+    }
+}
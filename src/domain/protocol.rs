@@ -1,24 +1,153 @@
 //! Protocol used to communicate with the printer
 
+use super::bidi;
 #[cfg(feature = "graphics")]
 use super::bit_image::*;
-use super::{character::*, codes::*, common::get_parameters_number_2, constants::*, types::*, RealTimeStatusRequest};
+use super::combining::{compose_text, decompose};
+use super::indic::IndicScript;
+use super::transliteration::transliterate;
+use super::{
+    character::*, code_page::CodePage, codes::*, common::get_parameters_number_2, constants::*, types::*,
+    RealTimeStatusRequest,
+};
 use crate::{
-    domain::page_codes::PageCodeTable,
+    domain::page_codes,
     errors::{PrinterError, Result},
     io::encoder::Encoder,
 };
+use std::borrow::Cow;
+
+/// Control characters allowed in the text by default (LF and HT)
+const DEFAULT_ALLOWED_CONTROL_CHARACTERS: [char; 2] = ['\n', '\t'];
 
 /// Protocol used to communicate with the printer
-#[derive(Default, Clone)]
+#[derive(Clone)]
 pub struct Protocol {
     encoder: Encoder,
+    unmappable_policy: UnmappablePolicy,
+    character_set: CharacterSet,
+    code_pages: Vec<CodePage>,
+    control_character_policy: ControlCharacterPolicy,
+    allowed_control_characters: Vec<char>,
+}
+
+impl Default for Protocol {
+    fn default() -> Self {
+        Self::new(Encoder::default())
+    }
 }
 
 impl Protocol {
     /// Create new protocol
     pub fn new(encoder: Encoder) -> Self {
-        Self { encoder }
+        Self {
+            encoder,
+            unmappable_policy: UnmappablePolicy::default(),
+            character_set: CharacterSet::default(),
+            code_pages: vec![],
+            control_character_policy: ControlCharacterPolicy::default(),
+            allowed_control_characters: DEFAULT_ALLOWED_CONTROL_CHARACTERS.to_vec(),
+        }
+    }
+
+    /// Set the behavior when a character is missing from the page code table
+    pub fn with_unmappable_policy(mut self, policy: UnmappablePolicy) -> Self {
+        self.unmappable_policy = policy;
+        self
+    }
+
+    /// Set the behavior when a character is missing from the page code table
+    pub(crate) fn set_unmappable_policy(&mut self, policy: UnmappablePolicy) {
+        self.unmappable_policy = policy;
+    }
+
+    /// Set the behavior for the control characters of the text (stripped by default)
+    pub fn with_control_character_policy(mut self, policy: ControlCharacterPolicy) -> Self {
+        self.control_character_policy = policy;
+        self
+    }
+
+    /// Set the behavior for the control characters of the text
+    pub(crate) fn set_control_character_policy(&mut self, policy: ControlCharacterPolicy) {
+        self.control_character_policy = policy;
+    }
+
+    /// Set the control characters allowed in the text (LF and HT by default)
+    pub fn with_allowed_control_characters(mut self, characters: &[char]) -> Self {
+        self.allowed_control_characters = characters.to_vec();
+        self
+    }
+
+    /// Set the control characters allowed in the text
+    pub(crate) fn set_allowed_control_characters(&mut self, characters: &[char]) {
+        self.allowed_control_characters = characters.to_vec();
+    }
+
+    /// Strip or escape the control characters of the text which are not allowed
+    pub(crate) fn sanitize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let forbidden = |c: char| c < ' ' && !self.allowed_control_characters.contains(&c);
+        if !text.chars().any(forbidden) {
+            return Cow::Borrowed(text);
+        }
+
+        let mut sanitized = String::with_capacity(text.len() + 8);
+        for c in text.chars() {
+            match (forbidden(c), self.control_character_policy) {
+                (false, _) => sanitized.push(c),
+                (true, ControlCharacterPolicy::Strip) => (),
+                (true, ControlCharacterPolicy::Escape) => {
+                    sanitized.push('^');
+                    sanitized.push(char::from(c as u8 + b'@'));
+                }
+            }
+        }
+        Cow::Owned(sanitized)
+    }
+
+    /// Register a code page replacing the built-in table and ESC t number of its page code
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     let code_page = CodePage::from(PageCode::PC866).with_number(59);
+    ///     let protocol = Protocol::default().with_code_page(code_page);
+    ///
+    ///     // ESC t 59 is sent to select PC866
+    ///     Printer::new(driver, protocol, Some(PageCode::PC866))
+    ///         .init()?
+    ///         .writeln("Привет")?
+    ///         .print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_code_page(mut self, code_page: CodePage) -> Self {
+        self.code_pages
+            .retain(|registered| registered.page_code() != code_page.page_code());
+        self.code_pages.push(code_page);
+        self
+    }
+
+    /// Code page registered for a page code
+    pub(crate) fn registered_code_page(&self, page_code: PageCode) -> Option<&CodePage> {
+        self.code_pages
+            .iter()
+            .find(|code_page| code_page.page_code() == page_code)
+    }
+
+    /// Code page of a page code, the registered one or the built-in one
+    pub(crate) fn code_page(&self, page_code: PageCode) -> &CodePage {
+        self.registered_code_page(page_code)
+            .unwrap_or_else(|| page_codes::code_page(page_code))
+    }
+
+    /// Set the international character set used to encode the text
+    pub(crate) fn set_character_set(&mut self, code: CharacterSet) {
+        self.character_set = code;
     }
 
     /// Initialization
@@ -47,8 +176,13 @@ impl Protocol {
 
     /// Character page code
     pub(crate) fn page_code(&self, code: PageCode) -> Command {
+        self.page_code_number(self.code_page(code).number())
+    }
+
+    /// Character page code from its ESC t number
+    pub(crate) fn page_code_number(&self, number: u8) -> Command {
         let mut cmd = ESC_CHARACTER_PAGE_CODE.to_vec();
-        cmd.push(code.into());
+        cmd.push(number);
         cmd
     }
 
@@ -59,6 +193,11 @@ impl Protocol {
         cmd
     }
 
+    /// Buffer encoding the text with the international character set
+    fn text_buffer(&self) -> TextBuffer<'_> {
+        TextBuffer::new(&self.encoder, self.unmappable_policy, self.character_set)
+    }
+
     /// Emphasis
     pub(crate) fn bold(&self, enabled: bool) -> Command {
         match enabled {
@@ -167,6 +306,263 @@ impl Protocol {
         }
     }
 
+    /// Print mode (font, bold, double height and width, underline)
+    pub(crate) fn print_mode(&self, mode: PrintMode) -> Result<Command> {
+        let font = match mode.font {
+            Font::A => 0,
+            Font::B => 1,
+            Font::C => return Err(PrinterError::Input(format!("invalid print mode font: {}", mode.font))),
+        };
+
+        let mut cmd = ESC_TEXT_PRINT_MODE.to_vec();
+        cmd.push(
+            font | u8::from(mode.bold) << 3
+                | u8::from(mode.double_height) << 4
+                | u8::from(mode.double_width) << 5
+                | u8::from(mode.underline) << 7,
+        );
+        Ok(cmd)
+    }
+
+    /// Right-side character spacing (in horizontal motion units)
+    pub(crate) fn character_spacing(&self, value: u8) -> Command {
+        let mut cmd = ESC_TEXT_CHARACTER_SPACING.to_vec();
+        cmd.push(value);
+        cmd
+    }
+
+    /// Unidirectional printing
+    pub(crate) fn unidirectional(&self, enabled: bool) -> Command {
+        match enabled {
+            true => ESC_UNIDIRECTIONAL_ON.to_vec(),
+            false => ESC_UNIDIRECTIONAL_OFF.to_vec(),
+        }
+    }
+
+    /// Character colour
+    pub(crate) fn character_colour(&self, colour: TextColour) -> Command {
+        let mut cmd = GS_TEXT_CHARACTER_COLOUR.to_vec();
+        cmd.push(colour.into());
+        cmd
+    }
+
+    /// Background colour
+    pub(crate) fn background_colour(&self, colour: TextColour) -> Command {
+        let mut cmd = GS_TEXT_BACKGROUND_COLOUR.to_vec();
+        cmd.push(colour.into());
+        cmd
+    }
+
+    /// Character shading
+    pub(crate) fn shading(&self, enabled: bool, colour: TextColour) -> Command {
+        let mut cmd = GS_TEXT_SHADING.to_vec();
+        cmd.push(48 + u8::from(enabled));
+        cmd.push(colour.into());
+        cmd
+    }
+
+    /// Absolute print position (in horizontal motion units from the beginning of the line)
+    pub(crate) fn absolute_position(&self, position: u16) -> Command {
+        let mut cmd = ESC_ABSOLUTE_POSITION.to_vec();
+        cmd.extend_from_slice(&position.to_le_bytes());
+        cmd
+    }
+
+    /// Relative print position (in horizontal motion units from the current position)
+    pub(crate) fn relative_position(&self, offset: i16) -> Command {
+        let mut cmd = ESC_RELATIVE_POSITION.to_vec();
+        cmd.extend_from_slice(&offset.to_le_bytes());
+        cmd
+    }
+
+    /// Horizontal tab positions (in characters, ascending, 32 maximum)
+    pub(crate) fn tab_positions(&self, positions: &[u8]) -> Result<Command> {
+        if positions.len() > 32 {
+            return Err(PrinterError::Input(format!(
+                "too many tab positions: {} (32 maximum)",
+                positions.len()
+            )));
+        }
+        if positions.first() == Some(&0) || positions.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(PrinterError::Input(format!("invalid tab positions: {positions:?}")));
+        }
+
+        let mut cmd = ESC_TAB_POSITIONS.to_vec();
+        cmd.extend_from_slice(positions);
+        cmd.push(NUL);
+        Ok(cmd)
+    }
+
+    /// Horizontal tab
+    pub(crate) fn tab(&self) -> Command {
+        vec![HT]
+    }
+
+    /// Move to the beginning of the line, after printing the data of the line or discarding it
+    pub(crate) fn line_start(&self, print: bool) -> Command {
+        let mut cmd = GS_LINE_START.to_vec();
+        cmd.push(u8::from(print));
+        cmd
+    }
+
+    /// Left margin (in horizontal motion units)
+    pub(crate) fn left_margin(&self, margin: u16) -> Command {
+        let mut cmd = GS_LEFT_MARGIN.to_vec();
+        cmd.extend_from_slice(&margin.to_le_bytes());
+        cmd
+    }
+
+    /// Print area width (in horizontal motion units)
+    pub(crate) fn print_area_width(&self, width: u16) -> Result<Command> {
+        if width == 0 {
+            return Err(PrinterError::Input("print area width cannot be equal to 0".to_owned()));
+        }
+
+        let mut cmd = GS_PRINT_AREA_WIDTH.to_vec();
+        cmd.extend_from_slice(&width.to_le_bytes());
+        Ok(cmd)
+    }
+
+    /// Page mode
+    pub(crate) fn page_mode(&self) -> Command {
+        ESC_PAGE_MODE.to_vec()
+    }
+
+    /// Standard mode
+    pub(crate) fn standard_mode(&self) -> Command {
+        ESC_STANDARD_MODE.to_vec()
+    }
+
+    /// Print area in page mode
+    pub(crate) fn page_area(&self, x: u16, y: u16, width: u16, height: u16) -> Result<Command> {
+        if width == 0 || height == 0 {
+            return Err(PrinterError::Input(format!(
+                "invalid page area size: {width} x {height}"
+            )));
+        }
+
+        let mut cmd = ESC_PAGE_AREA.to_vec();
+        for value in [x, y, width, height] {
+            cmd.extend_from_slice(&value.to_le_bytes());
+        }
+        Ok(cmd)
+    }
+
+    /// Print direction in page mode
+    pub(crate) fn page_direction(&self, direction: PageDirection) -> Command {
+        let mut cmd = ESC_PAGE_DIRECTION.to_vec();
+        cmd.push(direction.into());
+        cmd
+    }
+
+    /// Absolute vertical print position in page mode
+    pub(crate) fn vertical_position(&self, position: u16) -> Command {
+        let mut cmd = GS_PAGE_VERTICAL_POSITION.to_vec();
+        cmd.extend_from_slice(&position.to_le_bytes());
+        cmd
+    }
+
+    /// Relative vertical print position in page mode
+    pub(crate) fn relative_vertical_position(&self, offset: i16) -> Command {
+        let mut cmd = GS_PAGE_RELATIVE_VERTICAL_POSITION.to_vec();
+        cmd.extend_from_slice(&offset.to_le_bytes());
+        cmd
+    }
+
+    /// Print the data of page mode, then return to standard mode or stay in page mode
+    pub(crate) fn print_page(&self, exit: bool) -> Command {
+        match exit {
+            true => FF_PAGE_PRINT_EXIT.to_vec(),
+            false => ESC_PAGE_PRINT.to_vec(),
+        }
+    }
+
+    /// Kanji mode
+    pub(crate) fn kanji_mode(&self, enabled: bool) -> Command {
+        match enabled {
+            true => FS_KANJI_MODE_ON.to_vec(),
+            false => FS_KANJI_MODE_OFF.to_vec(),
+        }
+    }
+
+    /// Kanji character code system
+    pub(crate) fn kanji_code_system(&self, system: KanjiCodeSystem) -> Command {
+        let mut cmd = FS_KANJI_CODE_SYSTEM.to_vec();
+        cmd.push(system.into());
+        cmd
+    }
+
+    /// Kanji print mode
+    pub(crate) fn kanji_print_mode(&self, double_width: bool, double_height: bool, underline: bool) -> Command {
+        let mut cmd = FS_KANJI_PRINT_MODE.to_vec();
+        cmd.push((u8::from(double_width) << 2) | (u8::from(double_height) << 3) | (u8::from(underline) << 7));
+        cmd
+    }
+
+    /// Print text in Kanji mode with a double-byte encoding
+    pub(crate) fn kanji_text(&self, text: &str, encoding: KanjiEncoding) -> Result<Command> {
+        let encoder = Encoder::new(encoding.codec());
+        match self.unmappable_policy {
+            UnmappablePolicy::Encoder => encoder.encode(text),
+            UnmappablePolicy::Strict => encoder.with_replacement(None).encode(text),
+            UnmappablePolicy::Replace(byte) => {
+                // The byte is sent as is: as a character, bytes from 0x80 would not be encodable
+                let mut cmd = Vec::with_capacity(text.len());
+                for c in text.chars() {
+                    match encoding.codec().encode(c.encode_utf8(&mut [0; 4])) {
+                        (_, _, true) => cmd.push(byte),
+                        (bytes, _, false) => cmd.extend_from_slice(&bytes),
+                    }
+                }
+                Ok(cmd)
+            }
+            UnmappablePolicy::Transliterate => {
+                let text = text
+                    .chars()
+                    .map(|c| match encoding.codec().encode(c.encode_utf8(&mut [0; 4])).2 {
+                        true => transliterate(c),
+                        false => c.to_string(),
+                    })
+                    .collect::<String>();
+                encoder.encode(&text)
+            }
+        }
+    }
+
+    /// Unicode mode (UTF-8 or 1-byte character encoding)
+    pub(crate) fn unicode_mode(&self, enabled: bool) -> Command {
+        match enabled {
+            true => FS_CHARACTER_ENCODING_UTF8.to_vec(),
+            false => FS_CHARACTER_ENCODING_ONE_BYTE.to_vec(),
+        }
+    }
+
+    /// Font priorities of Unicode mode (first and second priority)
+    pub(crate) fn font_priority(&self, fonts: &[UnicodeFont]) -> Result<Vec<Command>> {
+        if fonts.len() > 2 {
+            return Err(PrinterError::Input(format!(
+                "too many font priorities: {} (2 maximum)",
+                fonts.len()
+            )));
+        }
+
+        Ok(fonts
+            .iter()
+            .enumerate()
+            .map(|(priority, &font)| {
+                let mut cmd = FS_FONT_PRIORITY.to_vec();
+                cmd.push(priority as u8);
+                cmd.push(font.into());
+                cmd
+            })
+            .collect())
+    }
+
+    /// Print text in Unicode mode (UTF-8)
+    pub(crate) fn unicode_text(&self, text: &str) -> Command {
+        text.as_bytes().to_vec()
+    }
+
     /// Cash drawer
     pub(crate) fn cash_drawer(&self, pin: CashDrawer) -> Command {
         match pin {
@@ -179,33 +575,168 @@ impl Protocol {
     pub(crate) fn text(&self, text: &str, page_code: Option<PageCode>) -> Result<Command> {
         match page_code {
             Some(page_code) => {
-                let table: PageCodeTable = page_code.try_into()?;
-                let table = table.get_table();
+                let table = self.code_page(page_code);
+                let mut buffer = self.text_buffer();
 
-                let mut cmd = Vec::new();
-                let mut current = String::new();
+                for (i, c) in compose_text(text) {
+                    if buffer.push_international(i, c)? {
+                        continue;
+                    }
 
-                for c in text.chars() {
-                    if let Some(&n) = table.get(&c) {
-                        if !current.is_empty() {
-                            cmd.append(&mut self.encoder.encode(&current)?);
-                            current.clear();
-                        }
-
-                        cmd.push(n);
-                    } else {
-                        current.push(c);
+                    match table.byte(c) {
+                        Some(n) => buffer.push_byte(n)?,
+                        None if c.is_ascii() => buffer.push_char(c),
+                        None => match decompose(c, |c| table.contains(c)) {
+                            Some(chars) => buffer.push_decomposed(&chars, table)?,
+                            None => buffer.push_unmappable(i, c, Some(table))?,
+                        },
                     }
                 }
 
-                if !current.is_empty() {
-                    cmd.append(&mut self.encoder.encode(&current)?);
+                buffer.finish(&format!("page code {page_code}"))
+            }
+            None if !self.character_set.replaces_ascii() => self.encoder.encode(text),
+            None => {
+                let mut buffer = self.text_buffer();
+
+                for (i, c) in text.chars().enumerate() {
+                    if !buffer.push_international(i, c)? {
+                        buffer.push_char(c);
+                    }
                 }
 
-                Ok(cmd)
+                buffer.finish(&format!("character set {}", self.character_set))
+            }
+        }
+    }
+
+    /// Print text in ISCII for an India character set
+    ///
+    /// Pre-base matras are moved before their consonant cluster, as the printer does not reorder them.
+    pub(crate) fn indic_text(&self, text: &str, script: IndicScript) -> Result<Command> {
+        let chars = script.visual_order(text);
+        let mut buffer = self.text_buffer();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            if c.is_ascii() {
+                buffer.push_char(c);
+                i += 1;
+                continue;
+            }
+
+            match script.encode(&chars[i..]) {
+                Some((bytes, length)) => {
+                    for byte in bytes {
+                        buffer.push_byte(byte)?;
+                    }
+                    i += length;
+                }
+                None => {
+                    buffer.push_unmappable(i, c, None)?;
+                    i += 1;
+                }
+            }
+        }
+
+        buffer.finish(&format!("{script} character set"))
+    }
+
+    /// Shape the Arabic letters of a line and reorder it for printing
+    ///
+    /// The presentation forms are used when they are available in the page code (or encoder without page code).
+    /// Returns the line in visual order and whether its base direction is right-to-left.
+    pub(crate) fn bidi_text(&self, line: &str, page_code: Option<PageCode>) -> Result<(String, bool)> {
+        let shaped = match page_code {
+            Some(page_code) => {
+                let table = self.code_page(page_code);
+                bidi::shape(line, |c| table.contains(c))
+            }
+            None => bidi::shape(line, |c| !self.encoder.codec().encode(c.encode_utf8(&mut [0; 4])).2),
+        };
+
+        Ok(bidi::reorder(&shaped))
+    }
+
+    /// Print text switching the page code for the characters missing from the current one
+    ///
+    /// For each run of characters, the page code covering the longest run is selected among `page_codes`
+    /// (page codes with their ESC t number). The original page code is restored at the end.
+    pub(crate) fn text_auto_page_code(
+        &self,
+        text: &str,
+        page_code: Option<PageCode>,
+        page_codes: &[(PageCode, u8)],
+    ) -> Result<Command> {
+        let tables = page_codes
+            .iter()
+            .map(|&(code, number)| (code, number, self.code_page(code)))
+            .collect::<Vec<_>>();
+        let (positions, chars): (Vec<_>, Vec<_>) = compose_text(text).into_iter().unzip();
+
+        let mut current = page_code.map(|page_code| (page_code, self.code_page(page_code)));
+        let mut buffer = self.text_buffer();
+
+        for (i, &c) in chars.iter().enumerate() {
+            if buffer.push_international(positions[i], c)? {
+                continue;
+            }
+            if c.is_ascii() {
+                buffer.push_char(c);
+                continue;
+            }
+
+            if let Some((_, table)) = current {
+                if let Some(byte) = table.byte(c) {
+                    buffer.push_byte(byte)?;
+                    continue;
+                }
+                if let Some(chars) = decompose(c, |c| table.contains(c)) {
+                    buffer.push_decomposed(&chars, table)?;
+                    continue;
+                }
+            }
+
+            // Page code covering the longest run of characters from here
+            let mut best: Option<(usize, (PageCode, u8, &CodePage))> = None;
+            for &(code, number, table) in tables.iter().filter(|(_, _, table)| table.contains(c)) {
+                let run = chars[i..]
+                    .iter()
+                    .take_while(|&&c| c.is_ascii() || table.contains(c))
+                    .count();
+                let longer = match best {
+                    Some((best_run, _)) => run > best_run,
+                    None => true,
+                };
+                if longer {
+                    best = Some((run, (code, number, table)));
+                }
+            }
+
+            match best {
+                Some((_, (code, number, table))) => {
+                    buffer.push_command(self.page_code_number(number))?;
+                    buffer.push_byte(table.byte(c).unwrap_or_default())?;
+                    current = Some((code, table));
+                }
+                None => buffer.push_unmappable(positions[i], c, current.map(|(_, table)| table))?,
+            }
+        }
+
+        // Restore the original page code (the default one if none was selected)
+        if let Some((code, _)) = current {
+            let original = page_code.unwrap_or_default();
+            if code != original {
+                let number = page_codes
+                    .iter()
+                    .find(|(code, _)| *code == original)
+                    .map_or(self.code_page(original).number(), |&(_, number)| number);
+                buffer.push_command(self.page_code_number(number))?;
             }
-            None => self.encoder.encode(text),
         }
+
+        buffer.finish("available page codes")
     }
 
     /// Set horizontal and vertical motion units
@@ -340,6 +871,49 @@ impl Protocol {
         ])
     }
 
+    #[cfg(all(feature = "codes_2d", feature = "graphics"))]
+    /// QR code printed as a raster bit image (for printers without native QR code)
+    pub(crate) fn qrcode_raster(&self, data: &str, option: QRCodeOption) -> Result<Command> {
+        use image::{DynamicImage, GrayImage, Luma};
+        use qrcode::{Color, EcLevel, QrCode};
+
+        // Quiet zone around the symbol in modules
+        const QUIET_ZONE: u32 = 4;
+
+        let level = match option.correction_level() {
+            QRCodeCorrectionLevel::L => EcLevel::L,
+            QRCodeCorrectionLevel::M => EcLevel::M,
+            QRCodeCorrectionLevel::Q => EcLevel::Q,
+            QRCodeCorrectionLevel::H => EcLevel::H,
+        };
+        let code = QrCode::with_error_correction_level(data.as_bytes(), level)
+            .map_err(|e| PrinterError::Input(format!("invalid QR code data: {e}")))?;
+
+        // Module size in dots, like GS ( k 167 (0 <=> 4)
+        let module = match option.size() {
+            0 => 4,
+            size => u32::from(size.min(15)),
+        };
+        let modules = u32::try_from(code.width())?;
+        let colors = code.to_colors();
+        let size = (modules + 2 * QUIET_ZONE) * module;
+        let image = GrayImage::from_fn(size, size, |x, y| {
+            let (x, y) = (x / module, y / module);
+            let dark = (QUIET_ZONE..QUIET_ZONE + modules).contains(&x)
+                && (QUIET_ZONE..QUIET_ZONE + modules).contains(&y)
+                && colors[((y - QUIET_ZONE) * modules + x - QUIET_ZONE) as usize] == Color::Dark;
+
+            Luma([if dark { 0 } else { 255 }])
+        });
+
+        let bit_image = BitImage::from_dynamic_image(
+            DynamicImage::ImageLuma8(image),
+            BitImageOption::new(None, None, BitImageSize::Normal)?,
+            "",
+        )?;
+        self.build_bit_image(bit_image)
+    }
+
     #[cfg(feature = "codes_2d")]
     /// 2D GS1 DataBar width
     fn gs1_databar_2d_width(&self, size: GS1DataBar2DWidth) -> Command {
@@ -704,6 +1278,119 @@ impl Protocol {
     // }
 }
 
+/// Text encoded with a page code table
+struct TextBuffer<'a> {
+    encoder: &'a Encoder,
+    policy: UnmappablePolicy,
+    character_set: CharacterSet,
+    cmd: Command,
+    pending: String,
+    missing: Vec<(usize, char)>,
+}
+
+impl<'a> TextBuffer<'a> {
+    /// Create a new `TextBuffer`
+    fn new(encoder: &'a Encoder, policy: UnmappablePolicy, character_set: CharacterSet) -> Self {
+        Self {
+            encoder,
+            policy,
+            character_set,
+            cmd: vec![],
+            pending: String::new(),
+            missing: vec![],
+        }
+    }
+
+    /// Encode the pending characters with the encoder
+    fn flush(&mut self) -> Result<()> {
+        if !self.pending.is_empty() {
+            self.cmd.append(&mut self.encoder.encode(&self.pending)?);
+            self.pending.clear();
+        }
+        Ok(())
+    }
+
+    /// Add a byte of the page code table
+    fn push_byte(&mut self, byte: u8) -> Result<()> {
+        self.flush()?;
+        self.cmd.push(byte);
+        Ok(())
+    }
+
+    /// Add a command
+    fn push_command(&mut self, mut cmd: Command) -> Result<()> {
+        self.flush()?;
+        self.cmd.append(&mut cmd);
+        Ok(())
+    }
+
+    /// Add a character encoded by the encoder
+    fn push_char(&mut self, c: char) {
+        self.pending.push(c);
+    }
+
+    /// Add a character moved to, or replaced at, an ASCII position by the international character set
+    ///
+    /// Returns `false` for the characters which are not affected by the character set.
+    fn push_international(&mut self, position: usize, c: char) -> Result<bool> {
+        match self.character_set.substitute(c) {
+            Some(byte) => self.push_byte(byte)?,
+            None if self.character_set.overrides(c) => self.push_unmappable(position, c, None)?,
+            None => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Add a character decomposed into characters of the page code table (or ASCII)
+    fn push_decomposed(&mut self, chars: &[char], table: &CodePage) -> Result<()> {
+        for &c in chars {
+            match table.byte(c) {
+                Some(byte) => self.push_byte(byte)?,
+                None => self.push_char(c),
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a character missing from the page code table
+    fn push_unmappable(&mut self, position: usize, c: char, table: Option<&CodePage>) -> Result<()> {
+        match self.policy {
+            UnmappablePolicy::Encoder if !self.character_set.overrides(c) => self.push_char(c),
+            UnmappablePolicy::Encoder => self.push_byte(b'?')?,
+            UnmappablePolicy::Strict => self.missing.push((position, c)),
+            UnmappablePolicy::Replace(byte) => self.push_byte(byte)?,
+            UnmappablePolicy::Transliterate => {
+                for c in transliterate(c).chars() {
+                    match table.and_then(|table| table.byte(c)) {
+                        Some(byte) => self.push_byte(byte)?,
+                        None if c.is_ascii() && !self.character_set.overrides(c) => self.push_char(c),
+                        None => self.push_byte(b'?')?,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the command, or an error listing the missing characters
+    fn finish(mut self, page_codes: &str) -> Result<Command> {
+        if !self.missing.is_empty() {
+            let missing = self
+                .missing
+                .iter()
+                .map(|(position, c)| format!("{c:?} at {position}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(PrinterError::Input(format!(
+                "characters missing from the {page_codes}: {missing}"
+            )));
+        }
+
+        self.flush()?;
+        Ok(self.cmd)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -740,6 +1427,147 @@ mod tests {
         assert_eq!(protocol.page_code(PageCode::PC858), vec![27, 116, 19]);
     }
 
+    #[test]
+    fn test_kanji_mode() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(protocol.kanji_mode(true), vec![28, 38]);
+        assert_eq!(protocol.kanji_mode(false), vec![28, 46]);
+        assert_eq!(protocol.kanji_code_system(KanjiCodeSystem::ShiftJis), vec![28, 67, 1]);
+        assert_eq!(protocol.kanji_print_mode(false, false, false), vec![28, 33, 0]);
+        assert_eq!(protocol.kanji_print_mode(true, true, true), vec![28, 33, 0x8C]);
+    }
+
+    #[test]
+    fn test_indic_text() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(
+            protocol.indic_text("नमस्ते 1", IndicScript::Devanagari).unwrap(),
+            vec![0xC6, 0xCC, 0xD7, 0xE8, 0xC2, 0xE1, b' ', b'1']
+        );
+
+        let protocol = protocol.with_unmappable_policy(UnmappablePolicy::Replace(b'?'));
+        assert_eq!(
+            protocol.indic_text("कि€", IndicScript::Devanagari).unwrap(),
+            vec![0xDB, 0xB3, b'?']
+        );
+
+        let protocol = protocol.with_unmappable_policy(UnmappablePolicy::Strict);
+        assert_eq!(
+            protocol
+                .indic_text("கா€", IndicScript::Devanagari)
+                .unwrap_err()
+                .to_string(),
+            "Input error: characters missing from the Devanagari character set: 'க' at 0, '\\u{bbe}' at 1, '€' at 2"
+        );
+    }
+
+    #[test]
+    fn test_kanji_text() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(
+            protocol.kanji_text("日本 1", KanjiEncoding::ShiftJis).unwrap(),
+            vec![0x93, 0xFA, 0x96, 0x7B, b' ', b'1']
+        );
+        assert_eq!(
+            protocol.kanji_text("中文", KanjiEncoding::Gb18030).unwrap(),
+            vec![0xD6, 0xD0, 0xCE, 0xC4]
+        );
+        assert_eq!(
+            protocol.kanji_text("中文", KanjiEncoding::Big5).unwrap(),
+            vec![0xA4, 0xA4, 0xA4, 0xE5]
+        );
+        assert_eq!(
+            protocol.kanji_text("한국", KanjiEncoding::EucKr).unwrap(),
+            vec![0xC7, 0xD1, 0xB1, 0xB9]
+        );
+        assert_eq!(protocol.kanji_text("한", KanjiEncoding::ShiftJis).unwrap(), vec![b'?']);
+
+        let protocol = protocol.with_unmappable_policy(UnmappablePolicy::Strict);
+        assert!(protocol.kanji_text("日한", KanjiEncoding::ShiftJis).is_err());
+
+        let protocol = protocol.with_unmappable_policy(UnmappablePolicy::Replace(0xA4));
+        assert_eq!(
+            protocol.kanji_text("日€本", KanjiEncoding::ShiftJis).unwrap(),
+            vec![0x93, 0xFA, 0xA4, 0x96, 0x7B]
+        );
+        let protocol = protocol.with_unmappable_policy(UnmappablePolicy::Replace(b'*'));
+        assert_eq!(
+            protocol.kanji_text("中한", KanjiEncoding::Big5).unwrap(),
+            vec![0xA4, 0xA4, b'*']
+        );
+
+        let protocol = protocol.with_unmappable_policy(UnmappablePolicy::Transliterate);
+        assert_eq!(protocol.kanji_text("5€", KanjiEncoding::ShiftJis).unwrap(), b"5EUR");
+    }
+
+    #[test]
+    fn test_unicode_mode() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(protocol.unicode_mode(true), vec![28, 40, 67, 2, 0, 48, 2]);
+        assert_eq!(protocol.unicode_mode(false), vec![28, 40, 67, 2, 0, 48, 1]);
+        assert_eq!(
+            protocol
+                .font_priority(&[UnicodeFont::Japanese, UnicodeFont::SimplifiedChinese])
+                .unwrap(),
+            vec![vec![28, 40, 67, 3, 0, 60, 0, 11], vec![28, 40, 67, 3, 0, 60, 1, 20]]
+        );
+        assert!(protocol
+            .font_priority(&[UnicodeFont::Ank, UnicodeFont::Japanese, UnicodeFont::Korean])
+            .is_err());
+        assert_eq!(protocol.unicode_text("Crème 日本"), "Crème 日本".as_bytes());
+    }
+
+    #[test]
+    fn test_bidi_text() {
+        let protocol = Protocol::new(Encoder::default());
+        let (line, rtl) = protocol.bidi_text("سلام 10", Some(PageCode::PC864)).unwrap();
+        assert_eq!(line, "10 \u{FEE1}\u{FEFC}\u{FEB3}");
+        assert!(rtl);
+        assert_eq!(
+            protocol.text(&line, Some(PageCode::PC864)).unwrap(),
+            vec![b'1', b'0', b' ', 0xEF, 0x9E, 0xD3]
+        );
+
+        // No presentation forms in WPC1256
+        let (line, _) = protocol.bidi_text("سلام", Some(PageCode::WPC1256)).unwrap();
+        assert_eq!(line, "مالس");
+    }
+
+    #[test]
+    fn test_text_combining_marks() {
+        let protocol = Protocol::new(Encoder::default());
+
+        // Thai combining vowels and tone marks follow their base character
+        assert_eq!(
+            protocol.text("ที่นี่", Some(PageCode::PC874)).unwrap(),
+            vec![0xB7, 0xD5, 0xE8, 0xB9, 0xD5, 0xE8]
+        );
+
+        // Vietnamese: precomposed when available, base + tone mark otherwise
+        assert_eq!(
+            protocol.text("Việt", Some(PageCode::WPC1258)).unwrap(),
+            vec![b'V', b'i', 0xEA, 0xF2, b't']
+        );
+        assert_eq!(protocol.text("e\u{0301}", Some(PageCode::PC437)).unwrap(), vec![0x82]);
+
+        // The positions of the missing characters are the ones of the text before composition
+        let protocol = protocol.with_unmappable_policy(UnmappablePolicy::Strict);
+        assert_eq!(
+            protocol
+                .text("e\u{0301}e\u{0301}€", Some(PageCode::PC437))
+                .unwrap_err()
+                .to_string(),
+            "Input error: characters missing from the page code PC437: '€' at 4"
+        );
+        assert_eq!(
+            protocol
+                .text_auto_page_code("e\u{0301}€✓", Some(PageCode::PC437), &[(PageCode::PC858, 19)])
+                .unwrap_err()
+                .to_string(),
+            "Input error: characters missing from the available page codes: '✓' at 3"
+        );
+    }
+
     #[test]
     fn test_character_set() {
         let protocol = Protocol::new(Encoder::default());
@@ -847,6 +1675,93 @@ mod tests {
         assert_eq!(protocol.upside_down(true), vec![27, 123, 1]);
     }
 
+    #[test]
+    fn test_horizontal_position() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(protocol.relative_position(-24), vec![27, 92, 232, 255]);
+        assert_eq!(
+            protocol.tab_positions(&[8, 16, 32]).unwrap(),
+            vec![27, 68, 8, 16, 32, 0]
+        );
+        assert_eq!(protocol.tab_positions(&[]).unwrap(), vec![27, 68, 0]);
+        assert!(protocol.tab_positions(&[16, 8]).is_err());
+        assert!(protocol.tab_positions(&[0, 8]).is_err());
+        assert!(protocol.tab_positions(&[1; 33]).is_err());
+        assert_eq!(protocol.tab(), vec![9]);
+        assert_eq!(protocol.line_start(true), vec![29, 84, 1]);
+    }
+
+    #[test]
+    fn test_print_mode() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(protocol.print_mode(PrintMode::new()).unwrap(), vec![27, 33, 0]);
+        assert_eq!(
+            protocol
+                .print_mode(
+                    PrintMode::new()
+                        .with_font(Font::B)
+                        .with_bold(true)
+                        .with_double_height(true)
+                        .with_double_width(true)
+                        .with_underline(true)
+                )
+                .unwrap(),
+            vec![27, 33, 185]
+        );
+        assert!(protocol.print_mode(PrintMode::new().with_font(Font::C)).is_err());
+        assert_eq!(protocol.character_spacing(4), vec![27, 32, 4]);
+        assert_eq!(protocol.unidirectional(true), vec![27, 85, 1]);
+    }
+
+    #[test]
+    fn test_character_effects() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(
+            protocol.character_colour(TextColour::Colour2),
+            vec![29, 40, 78, 2, 0, 48, 50]
+        );
+        assert_eq!(
+            protocol.background_colour(TextColour::None),
+            vec![29, 40, 78, 2, 0, 49, 48]
+        );
+        assert_eq!(
+            protocol.shading(true, TextColour::Colour1),
+            vec![29, 40, 78, 3, 0, 50, 49, 49]
+        );
+    }
+
+    #[test]
+    fn test_print_area() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(protocol.left_margin(300), vec![29, 76, 44, 1]);
+        assert_eq!(protocol.print_area_width(432).unwrap(), vec![29, 87, 176, 1]);
+        assert!(protocol.print_area_width(0).is_err());
+    }
+
+    #[test]
+    fn test_page_mode() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(protocol.page_mode(), vec![27, 76]);
+        assert_eq!(protocol.standard_mode(), vec![27, 83]);
+        assert_eq!(
+            protocol.page_area(0, 10, 512, 300).unwrap(),
+            vec![27, 87, 0, 0, 10, 0, 0, 2, 44, 1]
+        );
+        assert!(protocol.page_area(0, 0, 0, 300).is_err());
+        assert_eq!(protocol.page_direction(PageDirection::TopToBottom), vec![27, 84, 3]);
+        assert_eq!(protocol.vertical_position(300), vec![29, 36, 44, 1]);
+        assert_eq!(protocol.relative_vertical_position(-2), vec![29, 92, 254, 255]);
+        assert_eq!(protocol.print_page(true), vec![12]);
+        assert_eq!(protocol.print_page(false), vec![27, 12]);
+    }
+
+    #[test]
+    fn test_absolute_position() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(protocol.absolute_position(0), vec![27, 36, 0, 0]);
+        assert_eq!(protocol.absolute_position(300), vec![27, 36, 44, 1]);
+    }
+
     #[test]
     fn test_cash_drawer() {
         let protocol = Protocol::new(Encoder::default());
@@ -860,6 +1775,117 @@ mod tests {
         assert_eq!(protocol.text("My text", None).unwrap(), "My text".as_bytes());
     }
 
+    #[test]
+    fn test_sanitize() {
+        let protocol = Protocol::default();
+        assert!(matches!(protocol.sanitize("a\tb\nc"), Cow::Borrowed("a\tb\nc")));
+        assert_eq!(protocol.sanitize("\x1Bp\x00\x19\u{FA} é\r\n"), "p\u{FA} é\n");
+
+        let protocol = protocol
+            .with_control_character_policy(ControlCharacterPolicy::Escape)
+            .with_allowed_control_characters(&['\n']);
+        assert_eq!(protocol.sanitize("\x1D\x56\x00\t\x7F\n"), "^]V^@^I\x7F\n");
+    }
+
+    #[test]
+    fn test_text_character_set() {
+        let mut protocol = Protocol::new(Encoder::default());
+        protocol.set_character_set(CharacterSet::Germany);
+        assert_eq!(
+            protocol.text("ä [x] ß", Some(PageCode::PC437)).unwrap(),
+            &[0x7B, b' ', b'?', b'x', b'?', b' ', 0x7E]
+        );
+        assert_eq!(
+            protocol
+                .text_auto_page_code("Ü λ", Some(PageCode::PC437), &[(PageCode::PC737, 14)])
+                .unwrap(),
+            &[0x5D, b' ', 27, 116, 14, 0xA2, 27, 116, 0]
+        );
+
+        protocol.set_character_set(CharacterSet::France);
+        assert_eq!(protocol.text("é @", None).unwrap(), &[0x7B, b' ', b'?']);
+
+        protocol.set_character_set(CharacterSet::UK);
+        protocol.set_unmappable_policy(UnmappablePolicy::Transliterate);
+        assert_eq!(
+            protocol.text("£5 #", Some(PageCode::PC437)).unwrap(),
+            &[0x23, b'5', b' ', b'?']
+        );
+
+        protocol.set_character_set(CharacterSet::Spain2);
+        protocol.set_unmappable_policy(UnmappablePolicy::Strict);
+        assert_eq!(
+            protocol.text("Ñ@", Some(PageCode::PC437)).unwrap_err().to_string(),
+            "Input error: characters missing from the page code PC437: '@' at 1"
+        );
+
+        protocol.set_character_set(CharacterSet::USA);
+        assert_eq!(protocol.text("@ [", None).unwrap(), b"@ [");
+    }
+
+    #[test]
+    fn test_text_auto_page_code() {
+        let protocol = Protocol::new(Encoder::default());
+        let page_codes = [(PageCode::PC858, 19), (PageCode::PC737, 14), (PageCode::PC866, 17)];
+
+        assert_eq!(
+            protocol
+                .text_auto_page_code("è Σ Б €", Some(PageCode::PC858), &page_codes)
+                .unwrap(),
+            &[138, 32, 27, 116, 14, 145, 32, 27, 116, 17, 129, 32, 27, 116, 19, 213]
+        );
+        assert_eq!(
+            protocol
+                .text_auto_page_code("a Σ", Some(PageCode::PC858), &page_codes)
+                .unwrap(),
+            &[97, 32, 27, 116, 14, 145, 27, 116, 19]
+        );
+        assert_eq!(
+            protocol.text_auto_page_code("é", None, &page_codes).unwrap(),
+            &[27, 116, 19, 130, 27, 116, 0]
+        );
+        assert_eq!(
+            protocol.text_auto_page_code("✓", None, &page_codes).unwrap(),
+            "✓".as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_text_unmappable_policy() {
+        let text = "Ça coûte 5 € “net” ✓";
+        let protocol = Protocol::default();
+        assert_eq!(
+            protocol.text(text, Some(PageCode::PC437)).unwrap(),
+            [b"\x80a co\x96te 5 ".as_slice(), "€ “net” ✓".as_bytes()].concat()
+        );
+
+        let protocol = Protocol::default().with_unmappable_policy(UnmappablePolicy::Strict);
+        assert_eq!(
+            protocol.text(text, Some(PageCode::PC437)).unwrap_err().to_string(),
+            "Input error: characters missing from the page code PC437: '€' at 11, '“' at 13, '”' at 17, '✓' at 19"
+        );
+        assert!(protocol.text("Ça coûte", Some(PageCode::PC437)).is_ok());
+
+        let protocol = Protocol::default().with_unmappable_policy(UnmappablePolicy::Replace(b'_'));
+        assert_eq!(
+            protocol.text(text, Some(PageCode::PC437)).unwrap(),
+            b"\x80a co\x96te 5 _ _net_ _"
+        );
+
+        let protocol = Protocol::default().with_unmappable_policy(UnmappablePolicy::Transliterate);
+        assert_eq!(
+            protocol.text(text, Some(PageCode::PC437)).unwrap(),
+            b"\x80a co\x96te 5 EUR \"net\" ?"
+        );
+        assert_eq!(protocol.text("ﬁn ŷ ő", Some(PageCode::PC437)).unwrap(), b"fin y o");
+        assert_eq!(
+            protocol
+                .text_auto_page_code("Σ ✓", None, &[(PageCode::PC737, 14)])
+                .unwrap(),
+            &[27, 116, 14, 145, 32, 63, 27, 116, 0]
+        );
+    }
+
     #[test]
     fn test_text_with_page_code() {
         let protocol = Protocol::new(Encoder::default());
@@ -984,8 +2010,18 @@ mod tests {
             &[77, 121, 32, 116, 101, 120, 116, 32, 0x80, 32, 0xA3, 32, 0xBA]
         );
 
-        // With page code table not yet implemented
-        assert!(protocol.text("My text", Some(PageCode::Hiragana)).is_err());
+        assert_eq!(
+            protocol.text("My text あ ん", Some(PageCode::Hiragana)).unwrap(),
+            &[77, 121, 32, 116, 101, 120, 116, 32, 0xB1, 32, 0xDD]
+        );
+        assert_eq!(
+            protocol.text("My text ש ₪", Some(PageCode::WPC1255)).unwrap(),
+            &[77, 121, 32, 116, 101, 120, 116, 32, 0xF9, 32, 0xA4]
+        );
+        assert_eq!(
+            protocol.text("My text ư ₫", Some(PageCode::WPC1258)).unwrap(),
+            &[77, 121, 32, 116, 101, 120, 116, 32, 0xFD, 32, 0xFE]
+        );
     }
 
     #[test]
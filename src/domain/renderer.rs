@@ -0,0 +1,737 @@
+//! Renderer used to preview an ESC/POS job as an image
+
+#![cfg(feature = "graphics")]
+
+use super::{decoder::*, PageCode};
+use crate::errors::{PrinterError, Result};
+use image::{GrayImage, Luma};
+
+const WHITE: u8 = 255;
+const BLACK: u8 = 0;
+
+/// 5x7 glyphs of the printable ASCII characters (0x20 - 0x7E), one byte per column, LSB on top
+const GLYPHS: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // !
+    [0x00, 0x07, 0x00, 0x07, 0x00], // "
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // #
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // $
+    [0x23, 0x13, 0x08, 0x64, 0x62], // %
+    [0x36, 0x49, 0x55, 0x22, 0x50], // &
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // (
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // )
+    [0x08, 0x2A, 0x1C, 0x2A, 0x08], // *
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // +
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ,
+    [0x08, 0x08, 0x08, 0x08, 0x08], // -
+    [0x00, 0x60, 0x60, 0x00, 0x00], // .
+    [0x20, 0x10, 0x08, 0x04, 0x02], // /
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // 0
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // 1
+    [0x42, 0x61, 0x51, 0x49, 0x46], // 2
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // 3
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // 4
+    [0x27, 0x45, 0x45, 0x45, 0x39], // 5
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // 6
+    [0x01, 0x71, 0x09, 0x05, 0x03], // 7
+    [0x36, 0x49, 0x49, 0x49, 0x36], // 8
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // 9
+    [0x00, 0x36, 0x36, 0x00, 0x00], // :
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ;
+    [0x08, 0x14, 0x22, 0x41, 0x00], // <
+    [0x14, 0x14, 0x14, 0x14, 0x14], // =
+    [0x00, 0x41, 0x22, 0x14, 0x08], // >
+    [0x02, 0x01, 0x51, 0x09, 0x06], // ?
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // @
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // A
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // B
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // C
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // D
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // E
+    [0x7F, 0x09, 0x09, 0x01, 0x01], // F
+    [0x3E, 0x41, 0x41, 0x51, 0x32], // G
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // H
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // I
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // J
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // K
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // L
+    [0x7F, 0x02, 0x04, 0x02, 0x7F], // M
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // N
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // O
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // P
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // Q
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // R
+    [0x46, 0x49, 0x49, 0x49, 0x31], // S
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // T
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // U
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // V
+    [0x7F, 0x20, 0x18, 0x20, 0x7F], // W
+    [0x63, 0x14, 0x08, 0x14, 0x63], // X
+    [0x03, 0x04, 0x78, 0x04, 0x03], // Y
+    [0x61, 0x51, 0x49, 0x45, 0x43], // Z
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // [
+    [0x02, 0x04, 0x08, 0x10, 0x20], // \
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // ]
+    [0x04, 0x02, 0x01, 0x02, 0x04], // ^
+    [0x40, 0x40, 0x40, 0x40, 0x40], // _
+    [0x00, 0x01, 0x02, 0x04, 0x00], // `
+    [0x20, 0x54, 0x54, 0x54, 0x78], // a
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // b
+    [0x38, 0x44, 0x44, 0x44, 0x20], // c
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // d
+    [0x38, 0x54, 0x54, 0x54, 0x18], // e
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // f
+    [0x08, 0x14, 0x54, 0x54, 0x3C], // g
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // h
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // i
+    [0x20, 0x40, 0x44, 0x3D, 0x00], // j
+    [0x00, 0x7F, 0x10, 0x28, 0x44], // k
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // l
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // m
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // n
+    [0x38, 0x44, 0x44, 0x44, 0x38], // o
+    [0x7C, 0x14, 0x14, 0x14, 0x08], // p
+    [0x08, 0x14, 0x14, 0x18, 0x7C], // q
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // r
+    [0x48, 0x54, 0x54, 0x54, 0x20], // s
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // t
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // u
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // v
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // w
+    [0x44, 0x28, 0x10, 0x28, 0x44], // x
+    [0x0C, 0x50, 0x50, 0x50, 0x3C], // y
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // z
+    [0x00, 0x08, 0x36, 0x41, 0x00], // {
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // |
+    [0x00, 0x41, 0x36, 0x08, 0x00], // }
+    [0x02, 0x01, 0x02, 0x04, 0x02], // ~
+];
+
+/// Glyph drawn for the characters without bitmap
+const UNKNOWN_GLYPH: [u8; 5] = [0x7F, 0x41, 0x41, 0x41, 0x7F];
+
+/// Render option
+#[derive(Debug, Clone, Copy)]
+pub struct RenderOption {
+    /// Printable width in dots
+    paper_width: u32,
+    /// Dots per inch
+    dpi: u32,
+    /// Page code active at the beginning of the job
+    page_code: Option<PageCode>,
+}
+
+impl Default for RenderOption {
+    /// 80 mm paper (72 mm printable) at 203 dpi
+    fn default() -> Self {
+        Self {
+            paper_width: 576,
+            dpi: 203,
+            page_code: None,
+        }
+    }
+}
+
+impl RenderOption {
+    /// Create a new `RenderOption`
+    pub fn new(paper_width: u32, dpi: u32, page_code: Option<PageCode>) -> Result<Self> {
+        if paper_width == 0 {
+            return Err(PrinterError::Input(
+                "render paper width cannot be equal to 0".to_owned(),
+            ));
+        }
+        if dpi == 0 {
+            return Err(PrinterError::Input("render dpi cannot be equal to 0".to_owned()));
+        }
+
+        Ok(Self {
+            paper_width,
+            dpi,
+            page_code,
+        })
+    }
+
+    /// Get paper width in dots
+    pub fn paper_width(&self) -> u32 {
+        self.paper_width
+    }
+
+    /// Get dots per inch
+    pub fn dpi(&self) -> u32 {
+        self.dpi
+    }
+
+    /// Get page code
+    pub fn page_code(&self) -> Option<PageCode> {
+        self.page_code
+    }
+}
+
+/// Character waiting in the line buffer
+#[derive(Debug, Clone, Copy)]
+struct Cell {
+    glyph: [u8; 5],
+    width: u32,
+    height: u32,
+    bold: bool,
+    underline: u8,
+    reverse: bool,
+}
+
+/// Print settings tracked while rendering
+#[derive(Debug, Clone, Copy)]
+struct RenderState {
+    font: u8,
+    bold: bool,
+    underline: u8,
+    reverse: bool,
+    width: u8,
+    height: u8,
+    justify: u8,
+    line_spacing: Option<u32>,
+    left_margin: u32,
+    area_width: Option<u32>,
+    barcode_width: u32,
+    barcode_height: u32,
+    code_2d_size: u32,
+}
+
+impl Default for RenderState {
+    fn default() -> Self {
+        Self {
+            font: 0,
+            bold: false,
+            underline: 0,
+            reverse: false,
+            width: 1,
+            height: 1,
+            justify: 0,
+            line_spacing: None,
+            left_margin: 0,
+            area_width: None,
+            barcode_width: 3,
+            barcode_height: 162,
+            code_2d_size: 3,
+        }
+    }
+}
+
+impl RenderState {
+    /// Character cell size (width, height) in dots for the current font
+    fn font_size(&self) -> (u32, u32) {
+        match self.font {
+            1 => (9, 17),
+            2 => (9, 24),
+            _ => (12, 24),
+        }
+    }
+}
+
+/// Renderer
+///
+/// Interprets the commands produced by [`Protocol`](super::Protocol) and draws an approximation of the printed
+/// receipt. Text uses a built-in 5x7 bitmap font scaled to the printer font cell, barcodes and 2D codes are drawn
+/// as placeholders of the right size.
+///
+/// # Examples
+///
+/// ```rust
+/// use escpos::utils::*;
+///
+/// let renderer = Renderer::new(RenderOption::default());
+/// let image = renderer.render(&[27, 64, b'H', b'e', b'l', b'l', b'o', 10]);
+///
+/// assert_eq!(image.width(), 576);
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Renderer {
+    option: RenderOption,
+}
+
+impl Renderer {
+    /// Create a new `Renderer`
+    pub fn new(option: RenderOption) -> Self {
+        Self { option }
+    }
+
+    /// Render a byte stream into a grayscale image
+    pub fn render(&self, data: &[u8]) -> GrayImage {
+        let mut canvas = Canvas::new(self.option);
+
+        for instruction in Decoder::new(self.option.page_code).decode(data) {
+            canvas.apply(instruction.command);
+        }
+        canvas.finish()
+    }
+
+    /// Default line spacing in dots (1/6 inch)
+    fn default_line_spacing(option: &RenderOption) -> u32 {
+        option.dpi / 6
+    }
+}
+
+/// Drawing surface growing with the job
+struct Canvas {
+    option: RenderOption,
+    state: RenderState,
+    pixels: Vec<u8>,
+    y: u32,
+    line: Vec<Cell>,
+    line_width: u32,
+}
+
+impl Canvas {
+    fn new(option: RenderOption) -> Self {
+        Self {
+            option,
+            state: RenderState::default(),
+            pixels: vec![],
+            y: 0,
+            line: vec![],
+            line_width: 0,
+        }
+    }
+
+    /// Apply a decoded command
+    fn apply(&mut self, command: DecodedCommand) {
+        match command {
+            DecodedCommand::Text(text) => text.chars().for_each(|c| self.push_char(c)),
+            DecodedCommand::LineFeed => self.print_line(1),
+            DecodedCommand::Feed(n) => self.print_line(u32::from(n)),
+            DecodedCommand::Init => {
+                self.print_line(0);
+                self.state = RenderState::default();
+            }
+            DecodedCommand::Bold(enabled) => self.state.bold = enabled,
+            DecodedCommand::Underline(mode) => self.state.underline = mode,
+            DecodedCommand::Font(font) => self.state.font = font,
+            DecodedCommand::Reverse(enabled) => self.state.reverse = enabled,
+            DecodedCommand::PrintMode(mode) => {
+                self.state.font = mode & 1;
+                self.state.bold = mode & 0x08 != 0;
+                self.state.height = 1 + (mode >> 4 & 1);
+                self.state.width = 1 + (mode >> 5 & 1);
+                self.state.underline = mode >> 7;
+            }
+            DecodedCommand::TextSize { width, height } => {
+                self.state.width = width;
+                self.state.height = height;
+            }
+            DecodedCommand::Justify(mode) => self.state.justify = mode,
+            DecodedCommand::LeftMargin(margin) => self.state.left_margin = u32::from(margin),
+            DecodedCommand::PrintAreaWidth(width) => self.state.area_width = Some(u32::from(width)),
+            DecodedCommand::AbsolutePosition(position) => self.move_to(u32::from(position)),
+            DecodedCommand::RelativePosition(offset) => {
+                self.move_to(self.line_width.saturating_add_signed(i32::from(offset)))
+            }
+            DecodedCommand::LineSpacing(n) => self.state.line_spacing = Some(u32::from(n)),
+            DecodedCommand::ResetLineSpacing => self.state.line_spacing = None,
+            DecodedCommand::BarcodeWidth(n) => self.state.barcode_width = u32::from(n.clamp(1, 6)),
+            DecodedCommand::BarcodeHeight(n) => self.state.barcode_height = u32::from(n.max(1)),
+            DecodedCommand::Barcode { data, .. } => self.draw_barcode(&data),
+            DecodedCommand::Code2D {
+                function, parameters, ..
+            } => match (function, parameters.as_slice()) {
+                (67, [size, ..]) => self.state.code_2d_size = u32::from(*size).max(1),
+                (81, _) => self.draw_code_2d(),
+                _ => (),
+            },
+            DecodedCommand::BitImage {
+                mode,
+                width_bytes,
+                height,
+                data,
+            } => self.draw_bit_image(mode, u32::from(width_bytes), u32::from(height), &data),
+            DecodedCommand::Cut { .. } => self.draw_cut(),
+            _ => (),
+        }
+    }
+
+    /// Add a character to the line buffer, printing the line when it is full
+    fn push_char(&mut self, c: char) {
+        let (font_width, font_height) = self.state.font_size();
+        let width = font_width * u32::from(self.state.width);
+
+        if self.line_width + width > self.area_width() && !self.line.is_empty() {
+            self.print_line(1);
+        }
+
+        self.line.push(Cell {
+            glyph: Self::glyph(c),
+            width,
+            height: font_height * u32::from(self.state.height),
+            bold: self.state.bold,
+            underline: self.state.underline,
+            reverse: self.state.reverse,
+        });
+        self.line_width += width;
+    }
+
+    /// Move the print position forward to `x` dots from the beginning of the line
+    fn move_to(&mut self, x: u32) {
+        if x > self.line_width && x <= self.area_width() {
+            self.line.push(Cell {
+                glyph: Self::glyph(' '),
+                width: x - self.line_width,
+                height: 0,
+                bold: false,
+                underline: 0,
+                reverse: false,
+            });
+            self.line_width = x;
+        }
+    }
+
+    /// Get the glyph of a character
+    fn glyph(c: char) -> [u8; 5] {
+        let c = match c {
+            'À'..='Å' => 'A',
+            'Ç' => 'C',
+            'È'..='Ë' => 'E',
+            'Ì'..='Ï' => 'I',
+            'Ñ' => 'N',
+            'Ò'..='Ö' | 'Ø' => 'O',
+            'Ù'..='Ü' => 'U',
+            'Ý' => 'Y',
+            'à'..='å' => 'a',
+            'ç' => 'c',
+            'è'..='ë' => 'e',
+            'ì'..='ï' => 'i',
+            'ñ' => 'n',
+            'ò'..='ö' | 'ø' => 'o',
+            'ù'..='ü' => 'u',
+            'ý' | 'ÿ' => 'y',
+            '\u{00A0}' => ' ',
+            c => c,
+        };
+
+        match c {
+            ' '..='~' => GLYPHS[c as usize - 0x20],
+            _ => UNKNOWN_GLYPH,
+        }
+    }
+
+    /// Print the line buffer and feed `lines` lines
+    fn print_line(&mut self, lines: u32) {
+        let spacing = self
+            .state
+            .line_spacing
+            .unwrap_or_else(|| Renderer::default_line_spacing(&self.option));
+        let line_height = self.line.iter().map(|cell| cell.height).max().unwrap_or(0);
+
+        if !self.line.is_empty() {
+            let mut x = self.justified_x(self.line_width);
+            let cells = std::mem::take(&mut self.line);
+
+            for cell in cells {
+                self.draw_cell(x, self.y + line_height - cell.height, &cell);
+                x += cell.width;
+            }
+            self.line_width = 0;
+        }
+
+        if lines > 0 {
+            self.y += spacing.max(line_height) + spacing * (lines - 1);
+        } else {
+            self.y += line_height;
+        }
+        self.ensure_height(self.y);
+    }
+
+    /// Left margin in dots, within the paper
+    fn left_margin(&self) -> u32 {
+        self.state.left_margin.min(self.option.paper_width - 1)
+    }
+
+    /// Width of the print area in dots (from the left margin to the paper edge by default)
+    fn area_width(&self) -> u32 {
+        let available = self.option.paper_width - self.left_margin();
+        self.state
+            .area_width
+            .map_or(available, |width| width.clamp(1, available))
+    }
+
+    /// Get the left position of a block of `width` dots according to the justification
+    fn justified_x(&self, width: u32) -> u32 {
+        let free = self.area_width().saturating_sub(width);
+        self.left_margin()
+            + match self.state.justify {
+                1 => free / 2,
+                2 => free,
+                _ => 0,
+            }
+    }
+
+    /// Draw a character cell
+    fn draw_cell(&mut self, x: u32, y: u32, cell: &Cell) {
+        let (ink, paper) = match cell.reverse {
+            true => (WHITE, BLACK),
+            false => (BLACK, WHITE),
+        };
+        if cell.reverse {
+            self.fill(x, y, cell.width, cell.height, paper);
+        }
+
+        // 5x7 glyph in a 6x8 grid scaled to the cell
+        let scale_x = (cell.width / 6).max(1);
+        let scale_y = (cell.height / 8).max(1);
+        let offset_x = (cell.width - (5 * scale_x).min(cell.width)) / 2;
+
+        for (column, bits) in cell.glyph.iter().enumerate() {
+            for row in 0..7 {
+                if bits & (1 << row) != 0 {
+                    let px = x + offset_x + column as u32 * scale_x;
+                    let py = y + row * scale_y;
+                    let width = if cell.bold { scale_x + 1 } else { scale_x };
+                    self.fill(px, py, width, scale_y, ink);
+                }
+            }
+        }
+
+        if cell.underline > 0 {
+            self.fill(
+                x,
+                y + cell.height - cell.underline.min(2) as u32,
+                cell.width,
+                cell.underline.min(2) as u32,
+                ink,
+            );
+        }
+    }
+
+    /// Draw a GS v 0 raster image
+    fn draw_bit_image(&mut self, mode: u8, width_bytes: u32, height: u32, data: &[u8]) {
+        self.flush_pending_line();
+
+        let scale_x = if mode & 1 == 1 { 2 } else { 1 };
+        let scale_y = if mode & 2 == 2 { 2 } else { 1 };
+        let x0 = self.justified_x(width_bytes * 8 * scale_x);
+
+        self.ensure_height(self.y + height * scale_y);
+        for row in 0..height {
+            for column in 0..width_bytes * 8 {
+                let byte = data[(row * width_bytes + column / 8) as usize];
+                if byte & (0x80 >> (column % 8)) != 0 {
+                    self.fill(x0 + column * scale_x, self.y + row * scale_y, scale_x, scale_y, BLACK);
+                }
+            }
+        }
+        self.y += height * scale_y;
+    }
+
+    /// Draw a barcode placeholder: one module per data bit
+    fn draw_barcode(&mut self, data: &[u8]) {
+        self.flush_pending_line();
+
+        let module = self.state.barcode_width;
+        let height = self.state.barcode_height;
+        let width = (data.len() as u32 * 8 + 4) * module;
+        let x0 = self.justified_x(width);
+
+        self.ensure_height(self.y + height);
+        let bits = [0b1010_0000]
+            .iter()
+            .chain(data.iter())
+            .flat_map(|b| (0..8).map(move |i| b & (0x80 >> i) != 0));
+        for (i, black) in bits.enumerate() {
+            if black {
+                self.fill(x0 + i as u32 * module, self.y, module, height, BLACK);
+            }
+        }
+        self.y += height;
+    }
+
+    /// Draw a 2D code placeholder: a checkerboard of 21 x 21 modules
+    fn draw_code_2d(&mut self) {
+        self.flush_pending_line();
+
+        let module = self.state.code_2d_size;
+        let side = 21 * module;
+        let x0 = self.justified_x(side);
+
+        self.ensure_height(self.y + side);
+        for row in 0..21 {
+            for column in 0..21 {
+                let border = row == 0 || column == 0 || row == 20 || column == 20;
+                if border || (row + column) % 2 == 0 {
+                    self.fill(x0 + column * module, self.y + row * module, module, module, BLACK);
+                }
+            }
+        }
+        self.y += side;
+    }
+
+    /// Draw a dashed line where the paper is cut
+    fn draw_cut(&mut self) {
+        self.flush_pending_line();
+
+        let gap = self.option.dpi / 8;
+        self.ensure_height(self.y + gap);
+        for x in (0..self.option.paper_width).step_by(8) {
+            self.fill(x, self.y + gap / 2, 4, 1, BLACK);
+        }
+        self.y += gap;
+    }
+
+    /// Print pending characters before a graphic element
+    fn flush_pending_line(&mut self) {
+        if !self.line.is_empty() {
+            self.print_line(1);
+        }
+    }
+
+    /// Fill a rectangle, clipped to the paper width
+    fn fill(&mut self, x: u32, y: u32, width: u32, height: u32, value: u8) {
+        let paper_width = self.option.paper_width;
+        self.ensure_height(y + height);
+
+        for py in y..y + height {
+            for px in x..(x + width).min(paper_width) {
+                self.pixels[(py * paper_width + px) as usize] = value;
+            }
+        }
+    }
+
+    /// Grow the canvas to `height` rows
+    fn ensure_height(&mut self, height: u32) {
+        let size = (height * self.option.paper_width) as usize;
+        if self.pixels.len() < size {
+            self.pixels.resize(size, WHITE);
+        }
+    }
+
+    /// Print the pending line and build the image
+    fn finish(mut self) -> GrayImage {
+        self.flush_pending_line();
+
+        let width = self.option.paper_width;
+        let height = (self.pixels.len() as u32 / width).max(1);
+        self.pixels.resize((width * height) as usize, WHITE);
+
+        GrayImage::from_fn(width, height, |x, y| Luma([self.pixels[(y * width + x) as usize]]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{domain::*, io::encoder::Encoder};
+
+    fn black_pixels(image: &GrayImage) -> usize {
+        image.pixels().filter(|p| p.0[0] == BLACK).count()
+    }
+
+    #[test]
+    fn test_render_option() {
+        assert!(RenderOption::new(0, 203, None).is_err());
+        assert!(RenderOption::new(384, 0, None).is_err());
+
+        let option = RenderOption::new(384, 203, Some(PageCode::PC858)).unwrap();
+        assert_eq!(option.paper_width(), 384);
+        assert_eq!(option.dpi(), 203);
+    }
+
+    #[test]
+    fn test_render_text() {
+        let renderer = Renderer::new(RenderOption::new(384, 180, None).unwrap());
+        let image = renderer.render(b"ABC\n");
+
+        // One line of 30 dots (1/6 inch at 180 dpi)
+        assert_eq!(image.width(), 384);
+        assert_eq!(image.height(), 30);
+        assert!(black_pixels(&image) > 0);
+
+        // Nothing is drawn after the 3 characters of 12 dots
+        assert!((36..384).all(|x| (0..30).all(|y| image.get_pixel(x, y).0[0] == WHITE)));
+    }
+
+    #[test]
+    fn test_render_text_size_and_justify() {
+        let protocol = Protocol::new(Encoder::default());
+        let data = [
+            protocol.justify(JustifyMode::RIGHT),
+            protocol.text_size(2, 2).unwrap(),
+            b"A\n".to_vec(),
+        ]
+        .concat();
+        let image = Renderer::new(RenderOption::new(384, 180, None).unwrap()).render(&data);
+
+        // Character of 24 x 48 dots on the right
+        assert_eq!(image.height(), 48);
+        assert!((0..360).all(|x| (0..48).all(|y| image.get_pixel(x, y).0[0] == WHITE)));
+        assert!(black_pixels(&image) > 0);
+    }
+
+    #[test]
+    fn test_render_absolute_position() {
+        let protocol = Protocol::new(Encoder::default());
+        let data = [protocol.absolute_position(120), b"A\n".to_vec()].concat();
+        let image = Renderer::new(RenderOption::new(384, 180, None).unwrap()).render(&data);
+
+        // Character drawn from 120 dots
+        assert_eq!(image.height(), 30);
+        assert!((0..120).all(|x| (0..30).all(|y| image.get_pixel(x, y).0[0] == WHITE)));
+        assert!(black_pixels(&image) > 0);
+    }
+
+    #[test]
+    fn test_render_print_mode() {
+        let protocol = Protocol::new(Encoder::default());
+        let mode = PrintMode::new()
+            .with_font(Font::B)
+            .with_double_height(true)
+            .with_double_width(true);
+        let data = [protocol.print_mode(mode).unwrap(), b"AB\n".to_vec()].concat();
+        let image = Renderer::new(RenderOption::new(384, 180, None).unwrap()).render(&data);
+
+        // Font B characters of 18 x 34 dots
+        assert_eq!(image.height(), 34);
+        assert!((36..384).all(|x| (0..34).all(|y| image.get_pixel(x, y).0[0] == WHITE)));
+        assert!(black_pixels(&image) > 0);
+    }
+
+    #[test]
+    fn test_render_print_area() {
+        let protocol = Protocol::new(Encoder::default());
+        let data = [
+            protocol.left_margin(120),
+            protocol.print_area_width(120).unwrap(),
+            protocol.justify(JustifyMode::RIGHT),
+            b"ABCDEFGHIJK\n".to_vec(),
+        ]
+        .concat();
+        let image = Renderer::new(RenderOption::new(384, 180, None).unwrap()).render(&data);
+
+        // 10 characters on the first line and 1 on the second one, right-aligned in the area
+        assert_eq!(image.height(), 60);
+        assert!((0..120).all(|x| (0..60).all(|y| image.get_pixel(x, y).0[0] == WHITE)));
+        assert!((240..384).all(|x| (0..60).all(|y| image.get_pixel(x, y).0[0] == WHITE)));
+        assert!((120..228).all(|x| (30..60).all(|y| image.get_pixel(x, y).0[0] == WHITE)));
+        assert!(black_pixels(&image) > 0);
+    }
+
+    #[test]
+    fn test_render_reverse_and_wrap() {
+        let protocol = Protocol::new(Encoder::default());
+        let data = [protocol.reverse_colours(true), b"  ".to_vec()].concat();
+        let image = Renderer::new(RenderOption::new(12, 180, None).unwrap()).render(&data);
+
+        // Two lines of one reversed space
+        assert_eq!(image.height(), 60);
+        assert_eq!(black_pixels(&image), 12 * 24 * 2);
+    }
+
+    #[test]
+    fn test_render_bit_image_and_cut() {
+        let data = [GS, b'v', b'0', 0, 1, 0, 2, 0, 0xF0, 0x0F, GS, b'V', b'A', 0];
+        let image = Renderer::new(RenderOption::new(8, 160, None).unwrap()).render(&data);
+
+        assert_eq!(image.height(), 2 + 20);
+        assert_eq!(image.get_pixel(0, 0).0[0], BLACK);
+        assert_eq!(image.get_pixel(4, 0).0[0], WHITE);
+        assert_eq!(image.get_pixel(0, 1).0[0], WHITE);
+        assert_eq!(image.get_pixel(7, 1).0[0], BLACK);
+        assert_eq!(image.get_pixel(0, 12).0[0], BLACK);
+    }
+}
@@ -0,0 +1,150 @@
+//! Print state
+
+use super::{CharacterSet, Font, JustifyMode, PageCode, UnderlineMode};
+use std::fmt;
+
+/// Line spacing
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum LineSpacing {
+    /// Default line spacing (`ESC 2`)
+    #[default]
+    Default,
+    /// Line spacing in motion units (`ESC 3`)
+    Units(u8),
+}
+
+impl fmt::Display for LineSpacing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LineSpacing::Default => write!(f, "default"),
+            LineSpacing::Units(units) => write!(f, "{units} units"),
+        }
+    }
+}
+
+/// Print state
+///
+/// Settings sent to the printer, `None` while unknown (before `Printer::init`). The printer skips the commands
+/// which would not change them, and sends them again with `Printer::restore_state`.
+///
+/// # Examples
+///
+/// ```rust
+/// use escpos::printer::Printer;
+/// use escpos::utils::*;
+/// use escpos::{driver::*, errors::Result};
+///
+/// fn main() -> Result<()> {
+///     let driver = ConsoleDriver::open(false);
+///     let mut printer = Printer::new(driver, Protocol::default(), None);
+///     assert_eq!(printer.state().bold(), None);
+///
+///     printer.init()?.bold(true)?;
+///     assert_eq!(printer.state().bold(), Some(true));
+///     assert_eq!(printer.state().size(), Some((1, 1)));
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PrintState {
+    pub(crate) font: Option<Font>,
+    pub(crate) bold: Option<bool>,
+    pub(crate) underline: Option<UnderlineMode>,
+    pub(crate) size: Option<(u8, u8)>,
+    pub(crate) justify: Option<JustifyMode>,
+    pub(crate) reverse: Option<bool>,
+    pub(crate) line_spacing: Option<LineSpacing>,
+    pub(crate) motion_units: Option<(u8, u8)>,
+    pub(crate) left_margin: Option<u16>,
+    pub(crate) print_area_width: Option<u16>,
+    pub(crate) character_spacing: Option<u8>,
+    pub(crate) page_code: Option<PageCode>,
+    pub(crate) character_set: Option<CharacterSet>,
+}
+
+impl PrintState {
+    /// State after the initialization (`ESC @`), the page code and the print area width depending on the printer
+    pub(crate) fn initialized() -> Self {
+        Self {
+            font: Some(Font::A),
+            bold: Some(false),
+            underline: Some(UnderlineMode::None),
+            size: Some((1, 1)),
+            justify: Some(JustifyMode::LEFT),
+            reverse: Some(false),
+            line_spacing: Some(LineSpacing::Default),
+            motion_units: Some((0, 0)),
+            left_margin: Some(0),
+            print_area_width: None,
+            character_spacing: Some(0),
+            page_code: None,
+            character_set: Some(CharacterSet::default()),
+        }
+    }
+
+    /// Get font
+    pub fn font(&self) -> Option<Font> {
+        self.font
+    }
+
+    /// Get bold
+    pub fn bold(&self) -> Option<bool> {
+        self.bold
+    }
+
+    /// Get underline
+    pub fn underline(&self) -> Option<UnderlineMode> {
+        self.underline
+    }
+
+    /// Get text size
+    pub fn size(&self) -> Option<(u8, u8)> {
+        self.size
+    }
+
+    /// Get justification
+    pub fn justify(&self) -> Option<JustifyMode> {
+        self.justify
+    }
+
+    /// Get reverse colours
+    pub fn reverse(&self) -> Option<bool> {
+        self.reverse
+    }
+
+    /// Get line spacing
+    pub fn line_spacing(&self) -> Option<LineSpacing> {
+        self.line_spacing
+    }
+
+    /// Get horizontal and vertical motion units (`0` for the default unit)
+    pub fn motion_units(&self) -> Option<(u8, u8)> {
+        self.motion_units
+    }
+
+    /// Get left margin in dots
+    pub fn left_margin(&self) -> Option<u16> {
+        self.left_margin
+    }
+
+    /// Get print area width in dots
+    pub fn print_area_width(&self) -> Option<u16> {
+        self.print_area_width
+    }
+
+    /// Get right-side character spacing in horizontal motion units
+    pub fn character_spacing(&self) -> Option<u8> {
+        self.character_spacing
+    }
+
+    /// Get page code
+    pub fn page_code(&self) -> Option<PageCode> {
+        self.page_code
+    }
+
+    /// Get international character set
+    pub fn character_set(&self) -> Option<CharacterSet> {
+        self.character_set
+    }
+}
@@ -0,0 +1,100 @@
+//! Text style
+
+use super::{Font, JustifyMode, UnderlineMode};
+
+/// Text style
+///
+/// Settings applied by `Printer::push_style` and `Printer::with_style`, the ones left unset being unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// use escpos::utils::*;
+///
+/// let title = Style::new().with_bold(true).with_size(2, 2).with_justify(JustifyMode::CENTER);
+/// assert_eq!(title.bold(), Some(true));
+/// assert_eq!(title.size(), Some((2, 2)));
+/// assert_eq!(title.font(), None);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Style {
+    pub(crate) bold: Option<bool>,
+    pub(crate) underline: Option<UnderlineMode>,
+    pub(crate) font: Option<Font>,
+    pub(crate) size: Option<(u8, u8)>,
+    pub(crate) justify: Option<JustifyMode>,
+    pub(crate) reverse: Option<bool>,
+}
+
+impl Style {
+    /// Create a new style without setting
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set bold
+    pub fn with_bold(mut self, enabled: bool) -> Self {
+        self.bold = Some(enabled);
+        self
+    }
+
+    /// Set underline
+    pub fn with_underline(mut self, mode: UnderlineMode) -> Self {
+        self.underline = Some(mode);
+        self
+    }
+
+    /// Set font
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Set text size (1 to 8)
+    pub fn with_size(mut self, width: u8, height: u8) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    /// Set justification
+    pub fn with_justify(mut self, mode: JustifyMode) -> Self {
+        self.justify = Some(mode);
+        self
+    }
+
+    /// Set reverse colours
+    pub fn with_reverse(mut self, enabled: bool) -> Self {
+        self.reverse = Some(enabled);
+        self
+    }
+
+    /// Get bold
+    pub fn bold(&self) -> Option<bool> {
+        self.bold
+    }
+
+    /// Get underline
+    pub fn underline(&self) -> Option<UnderlineMode> {
+        self.underline
+    }
+
+    /// Get font
+    pub fn font(&self) -> Option<Font> {
+        self.font
+    }
+
+    /// Get text size
+    pub fn size(&self) -> Option<(u8, u8)> {
+        self.size
+    }
+
+    /// Get justification
+    pub fn justify(&self) -> Option<JustifyMode> {
+        self.justify
+    }
+
+    /// Get reverse colours
+    pub fn reverse(&self) -> Option<bool> {
+        self.reverse
+    }
+}
@@ -0,0 +1,410 @@
+//! Table layout used to align the columns of a receipt
+
+use super::width::{char_width, text_width, wrap_text};
+use crate::errors::{PrinterError, Result};
+use std::fmt;
+
+/// Width of a table column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnWidth {
+    /// Number of characters
+    Fixed(usize),
+    /// Share of the characters left by the fixed columns (weight)
+    Proportional(usize),
+}
+
+/// Alignment of the text in a table column
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+    /// Aligned on the decimal separator (`.` or `,`), followed by the number of decimals
+    Decimal(usize),
+}
+
+impl fmt::Display for ColumnAlign {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColumnAlign::Left => write!(f, "left"),
+            ColumnAlign::Center => write!(f, "center"),
+            ColumnAlign::Right => write!(f, "right"),
+            ColumnAlign::Decimal(decimals) => write!(f, "decimal ({decimals} decimals)"),
+        }
+    }
+}
+
+/// Behavior for the text wider than its column
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnOverflow {
+    /// Word-wrapped on several lines (default)
+    #[default]
+    Wrap,
+    /// Cut at the column width
+    Truncate,
+}
+
+impl fmt::Display for ColumnOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColumnOverflow::Wrap => write!(f, "wrap"),
+            ColumnOverflow::Truncate => write!(f, "truncate"),
+        }
+    }
+}
+
+/// Table column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Column {
+    width: ColumnWidth,
+    align: ColumnAlign,
+    overflow: ColumnOverflow,
+    bold: bool,
+}
+
+impl Column {
+    /// Create a new left-aligned column wrapping its text
+    pub fn new(width: ColumnWidth) -> Self {
+        Self {
+            width,
+            align: ColumnAlign::default(),
+            overflow: ColumnOverflow::default(),
+            bold: false,
+        }
+    }
+
+    /// Set the alignment
+    pub fn with_align(mut self, align: ColumnAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Set the behavior for the text wider than the column
+    pub fn with_overflow(mut self, overflow: ColumnOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Print the column in bold
+    pub fn with_bold(mut self, bold: bool) -> Self {
+        self.bold = bold;
+        self
+    }
+
+    /// Get width
+    pub fn width(&self) -> ColumnWidth {
+        self.width
+    }
+
+    /// Get alignment
+    pub fn align(&self) -> ColumnAlign {
+        self.align
+    }
+
+    /// Get overflow behavior
+    pub fn overflow(&self) -> ColumnOverflow {
+        self.overflow
+    }
+
+    /// Get bold
+    pub fn bold(&self) -> bool {
+        self.bold
+    }
+}
+
+/// Text of a cell on a printed line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TableSegment {
+    /// Position of the first character on the line (in characters)
+    pub(crate) start: usize,
+    pub(crate) text: String,
+    pub(crate) bold: bool,
+}
+
+/// Table
+///
+/// Columns of fixed or proportional width, printed row by row with `Printer::table_row`.
+/// The cells are padded with spaces, or placed with absolute positions (`ESC $`) so that the columns stay
+/// aligned when the printed characters do not have the expected width.
+///
+/// # Examples
+///
+/// ```rust
+/// use escpos::utils::*;
+///
+/// let table = Table::new(vec![
+///     Column::new(ColumnWidth::Fixed(3)).with_align(ColumnAlign::Right),
+///     Column::new(ColumnWidth::Proportional(1)),
+///     Column::new(ColumnWidth::Fixed(9)).with_align(ColumnAlign::Decimal(2)),
+/// ]);
+/// assert_eq!(table.widths(42).unwrap(), vec![3, 28, 9]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    columns: Vec<Column>,
+    gap: usize,
+    absolute_positioning: bool,
+}
+
+impl Table {
+    /// Create a new table with one space between the columns
+    pub fn new(columns: Vec<Column>) -> Self {
+        Self {
+            columns,
+            gap: 1,
+            absolute_positioning: false,
+        }
+    }
+
+    /// Set the number of characters between the columns
+    pub fn with_gap(mut self, gap: usize) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Place the cells with absolute positions (`ESC $`) instead of spaces
+    pub fn with_absolute_positioning(mut self, enabled: bool) -> Self {
+        self.absolute_positioning = enabled;
+        self
+    }
+
+    /// Get columns
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Get gap
+    pub fn gap(&self) -> usize {
+        self.gap
+    }
+
+    /// Get absolute positioning
+    pub fn absolute_positioning(&self) -> bool {
+        self.absolute_positioning
+    }
+
+    /// Widths of the columns (in characters) on a line of `columns` characters
+    ///
+    /// The characters left by the fixed columns and the gaps are shared between the proportional columns
+    /// according to their weight.
+    pub fn widths(&self, columns: usize) -> Result<Vec<usize>> {
+        let gaps = self.gap * self.columns.len().saturating_sub(1);
+        let fixed = self
+            .columns
+            .iter()
+            .map(|column| match column.width {
+                ColumnWidth::Fixed(width) => width,
+                ColumnWidth::Proportional(_) => 0,
+            })
+            .sum::<usize>();
+        let weights = self
+            .columns
+            .iter()
+            .map(|column| match column.width {
+                ColumnWidth::Fixed(_) => 0,
+                ColumnWidth::Proportional(weight) => weight,
+            })
+            .sum::<usize>();
+
+        if fixed + gaps > columns {
+            return Err(PrinterError::Input(format!(
+                "table wider than the line: {} characters for {columns}",
+                fixed + gaps
+            )));
+        }
+
+        let free = columns - fixed - gaps;
+        let mut left = free;
+        let mut widths = vec![];
+        for column in &self.columns {
+            let width = match column.width {
+                ColumnWidth::Fixed(width) => width,
+                ColumnWidth::Proportional(weight) => {
+                    let width = (free * weight).checked_div(weights).unwrap_or(0);
+                    left -= width;
+                    width
+                }
+            };
+            widths.push(width);
+        }
+
+        // Characters lost by the rounding go to the first proportional columns
+        for (width, column) in widths.iter_mut().zip(&self.columns) {
+            if left > 0 && matches!(column.width, ColumnWidth::Proportional(weight) if weight > 0) {
+                *width += 1;
+                left -= 1;
+            }
+        }
+
+        match widths.iter().position(|&width| width == 0) {
+            Some(i) => Err(PrinterError::Input(format!("table column {i} has no width"))),
+            None => Ok(widths),
+        }
+    }
+
+    /// Printed lines of a row, with the cells placed in the columns of the given widths
+    pub(crate) fn lines(&self, widths: &[usize], cells: &[&str]) -> Result<Vec<Vec<TableSegment>>> {
+        if cells.len() != self.columns.len() {
+            return Err(PrinterError::Input(format!(
+                "invalid table row: {} cells for {} columns",
+                cells.len(),
+                self.columns.len()
+            )));
+        }
+
+        let mut lines: Vec<Vec<TableSegment>> = vec![];
+        let mut start = 0;
+        for ((column, &width), cell) in self.columns.iter().zip(widths).zip(cells) {
+            let texts = match column.overflow {
+                ColumnOverflow::Wrap => wrap_text(cell, width, false),
+                ColumnOverflow::Truncate => cell.lines().take(1).map(|line| truncate(line, width)).collect(),
+            };
+
+            for (i, text) in texts.into_iter().filter(|text| !text.is_empty()).enumerate() {
+                if lines.len() <= i {
+                    lines.push(vec![]);
+                }
+                lines[i].push(TableSegment {
+                    start: start + offset(&text, width, column.align),
+                    text,
+                    bold: column.bold,
+                });
+            }
+            start += width + self.gap;
+        }
+
+        if lines.is_empty() {
+            lines.push(vec![]);
+        }
+
+        Ok(lines)
+    }
+}
+
+/// Position of a text in a column of `width` characters
+fn offset(text: &str, width: usize, align: ColumnAlign) -> usize {
+    let free = width.saturating_sub(text_width(text));
+    match align {
+        ColumnAlign::Left => 0,
+        ColumnAlign::Center => free / 2,
+        ColumnAlign::Right => free,
+        ColumnAlign::Decimal(decimals) => {
+            let integer = text.rfind(['.', ',']).map_or(text, |separator| &text[..separator]);
+            let fraction = decimals + usize::from(decimals > 0);
+            width.saturating_sub(fraction + text_width(integer)).min(free)
+        }
+    }
+}
+
+/// Text cut at `columns` characters, the combining marks staying with their base character
+fn truncate(text: &str, columns: usize) -> String {
+    let mut width = 0;
+    text.chars()
+        .take_while(|&c| {
+            width += char_width(c);
+            width <= columns
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start: usize, text: &str, bold: bool) -> TableSegment {
+        TableSegment {
+            start,
+            text: text.to_string(),
+            bold,
+        }
+    }
+
+    #[test]
+    fn test_widths() {
+        let table = Table::new(vec![
+            Column::new(ColumnWidth::Proportional(2)),
+            Column::new(ColumnWidth::Fixed(8)),
+            Column::new(ColumnWidth::Proportional(1)),
+        ]);
+        assert_eq!(table.widths(32).unwrap(), vec![15, 8, 7]);
+        assert_eq!(table.clone().with_gap(0).widths(32).unwrap(), vec![16, 8, 8]);
+
+        assert_eq!(
+            table.widths(9).unwrap_err().to_string(),
+            "Input error: table wider than the line: 10 characters for 9"
+        );
+        assert_eq!(
+            table.widths(11).unwrap_err().to_string(),
+            "Input error: table column 2 has no width"
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        let table = Table::new(vec![
+            Column::new(ColumnWidth::Fixed(2)).with_align(ColumnAlign::Right),
+            Column::new(ColumnWidth::Proportional(1)),
+            Column::new(ColumnWidth::Fixed(8))
+                .with_align(ColumnAlign::Decimal(2))
+                .with_bold(true),
+        ]);
+        let widths = table.widths(24).unwrap();
+        assert_eq!(widths, vec![2, 12, 8]);
+
+        assert_eq!(
+            table.lines(&widths, &["1", "Crème brûlée", "4.5"]).unwrap(),
+            vec![vec![
+                segment(1, "1", false),
+                segment(3, "Crème brûlée", false),
+                segment(20, "4.5", true)
+            ]]
+        );
+        assert_eq!(
+            table
+                .lines(&widths, &["12", "Macbook Pro with Retina", "2500"])
+                .unwrap(),
+            vec![
+                vec![
+                    segment(0, "12", false),
+                    segment(3, "Macbook Pro", false),
+                    segment(17, "2500", true)
+                ],
+                vec![segment(3, "with Retina", false)],
+            ]
+        );
+        assert_eq!(table.lines(&widths, &["", "", ""]).unwrap(), vec![vec![]]);
+        assert_eq!(
+            table.lines(&widths, &["1", "iMac"]).unwrap_err().to_string(),
+            "Input error: invalid table row: 2 cells for 3 columns"
+        );
+    }
+
+    #[test]
+    fn test_offset() {
+        assert_eq!(offset("ab", 6, ColumnAlign::Left), 0);
+        assert_eq!(offset("ab", 6, ColumnAlign::Center), 2);
+        assert_eq!(offset("ab", 6, ColumnAlign::Right), 4);
+        assert_eq!(offset("北京", 6, ColumnAlign::Right), 2);
+
+        assert_eq!(offset("12.50", 8, ColumnAlign::Decimal(2)), 3);
+        assert_eq!(offset("1250,5", 8, ColumnAlign::Decimal(2)), 1);
+        assert_eq!(offset("3", 8, ColumnAlign::Decimal(2)), 4);
+        assert_eq!(offset("7", 8, ColumnAlign::Decimal(0)), 7);
+        assert_eq!(offset("1.23456", 8, ColumnAlign::Decimal(2)), 1);
+    }
+
+    #[test]
+    fn test_truncate() {
+        let table = Table::new(vec![
+            Column::new(ColumnWidth::Fixed(5)).with_overflow(ColumnOverflow::Truncate),
+            Column::new(ColumnWidth::Fixed(3)).with_overflow(ColumnOverflow::Truncate),
+        ]);
+
+        assert_eq!(
+            table.lines(&[5, 3], &["Crème brûlée", "北京"]).unwrap(),
+            vec![vec![segment(0, "Crème", false), segment(6, "北", false)]]
+        );
+        assert_eq!(truncate("Vie\u{0323}\u{0302}t Nam", 4), "Vie\u{0323}\u{0302}t");
+    }
+}
@@ -0,0 +1,84 @@
+//! Transliteration of characters missing from the page codes
+
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// Characters which are not decomposed by NFKD
+const TRANSLITERATIONS: [(char, &str); 38] = [
+    ('€', "EUR"),
+    ('£', "GBP"),
+    ('¥', "JPY"),
+    ('¢', "c"),
+    ('“', "\""),
+    ('”', "\""),
+    ('„', "\""),
+    ('«', "\""),
+    ('»', "\""),
+    ('‘', "'"),
+    ('’', "'"),
+    ('‚', "'"),
+    ('‹', "'"),
+    ('›', "'"),
+    ('–', "-"),
+    ('—', "-"),
+    ('−', "-"),
+    ('•', "*"),
+    ('·', "."),
+    ('×', "x"),
+    ('÷', "/"),
+    ('©', "(C)"),
+    ('®', "(R)"),
+    ('æ', "ae"),
+    ('Æ', "AE"),
+    ('œ', "oe"),
+    ('Œ', "OE"),
+    ('ß', "ss"),
+    ('ø', "o"),
+    ('Ø', "O"),
+    ('đ', "d"),
+    ('Đ', "D"),
+    ('ð', "d"),
+    ('Ð', "D"),
+    ('ł', "l"),
+    ('Ł', "L"),
+    ('þ', "th"),
+    ('Þ', "TH"),
+];
+
+/// Transliterate a character into (mostly ASCII) characters
+///
+/// Known symbols are replaced by their ASCII equivalent, other characters are decomposed (NFKD)
+/// and their combining marks are removed (`é` → `e`, `ﬁ` → `fi`, `…` → `...`).
+pub(crate) fn transliterate(c: char) -> String {
+    if let Some((_, transliteration)) = TRANSLITERATIONS.iter().find(|(from, _)| *from == c) {
+        return transliteration.to_string();
+    }
+
+    c.nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .map(|c| match TRANSLITERATIONS.iter().find(|(from, _)| *from == c) {
+            Some((_, transliteration)) => transliteration.to_string(),
+            None => c.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transliterate() {
+        assert_eq!(transliterate('é'), "e");
+        assert_eq!(transliterate('Ç'), "C");
+        assert_eq!(transliterate('ŷ'), "y");
+        assert_eq!(transliterate('€'), "EUR");
+        assert_eq!(transliterate('“'), "\"");
+        assert_eq!(transliterate('’'), "'");
+        assert_eq!(transliterate('œ'), "oe");
+        assert_eq!(transliterate('ﬁ'), "fi");
+        assert_eq!(transliterate('…'), "...");
+        assert_eq!(transliterate('²'), "2");
+        assert_eq!(transliterate('\u{00A0}'), " ");
+        assert_eq!(transliterate('Б'), "Б");
+    }
+}
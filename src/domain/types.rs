@@ -19,7 +19,7 @@ impl fmt::Display for CashDrawer {
 }
 
 /// Justify mode
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum JustifyMode {
     LEFT,
     CENTER,
@@ -36,6 +36,49 @@ impl fmt::Display for JustifyMode {
     }
 }
 
+/// Print direction in page mode
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum PageDirection {
+    /// Left to right, starting at the upper left corner (0°)
+    #[default]
+    LeftToRight,
+    /// Bottom to top, starting at the lower left corner (90°)
+    BottomToTop,
+    /// Right to left, starting at the lower right corner (180°)
+    RightToLeft,
+    /// Top to bottom, starting at the upper right corner (270°)
+    TopToBottom,
+}
+
+impl PageDirection {
+    /// Check if the lines are printed across the paper (90° and 270°)
+    pub fn is_vertical(&self) -> bool {
+        matches!(self, PageDirection::BottomToTop | PageDirection::TopToBottom)
+    }
+}
+
+impl fmt::Display for PageDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PageDirection::LeftToRight => write!(f, "left to right (0°)"),
+            PageDirection::BottomToTop => write!(f, "bottom to top (90°)"),
+            PageDirection::RightToLeft => write!(f, "right to left (180°)"),
+            PageDirection::TopToBottom => write!(f, "top to bottom (270°)"),
+        }
+    }
+}
+
+impl From<PageDirection> for u8 {
+    fn from(value: PageDirection) -> Self {
+        match value {
+            PageDirection::LeftToRight => 0,
+            PageDirection::BottomToTop => 1,
+            PageDirection::RightToLeft => 2,
+            PageDirection::TopToBottom => 3,
+        }
+    }
+}
+
 /// Debug mode (decimal or hexadecimal)
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DebugMode {
@@ -0,0 +1,157 @@
+//! Width of the printed text in columns
+
+use unicode_width::UnicodeWidthChar;
+
+/// Width of a character in columns
+///
+/// CJK ideographs, Hangul and full-width forms take two columns (one Kanji character),
+/// combining marks (Thai vowels and tone marks, Vietnamese tone marks...) and control characters take none.
+pub fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+/// Width of a text in columns
+///
+/// ```rust
+/// use escpos::utils::text_width;
+///
+/// assert_eq!(text_width("Total"), 5);
+/// assert_eq!(text_width("ラーメン 800"), 12);
+/// assert_eq!(text_width("牛肉面"), 6);
+/// ```
+pub fn text_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// Wrap a text into lines of at most `columns` columns
+///
+/// Lines are broken between words, the spaces at the breaks being removed. Words longer than a line start
+/// on a new line and are broken at its end, with a hyphen if `hyphenate` is set (except between wide characters).
+///
+/// ```rust
+/// use escpos::utils::wrap_text;
+///
+/// assert_eq!(wrap_text("Crème brûlée with vanilla", 12, false), vec!["Crème brûlée", "with vanilla"]);
+/// assert_eq!(wrap_text("Supercalifragilistic", 8, true), vec!["Superca-", "lifragi-", "listic"]);
+/// ```
+pub fn wrap_text(text: &str, columns: usize, hyphenate: bool) -> Vec<String> {
+    let hyphenate = hyphenate && columns > 1;
+    let mut lines = vec![];
+
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        let mut width = 0;
+
+        for word in paragraph.split_whitespace() {
+            let word_width = text_width(word);
+            let separator = usize::from(!line.is_empty());
+
+            if width + separator + word_width <= columns {
+                if separator > 0 {
+                    line.push(' ');
+                }
+                line.push_str(word);
+                width += separator + word_width;
+                continue;
+            }
+
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+                width = 0;
+            }
+
+            // Break the word when the rest of it does not fit on the line
+            let chars = word.chars().collect::<Vec<_>>();
+            let mut remaining = word_width;
+            for (i, &c) in chars.iter().enumerate() {
+                let c_width = char_width(c);
+                if c_width > 0 && width > 0 && width + remaining > columns {
+                    // Keep room for the hyphen added if the word is broken between `c` and the next character
+                    let next_width = chars[i + 1..].iter().map(|&c| char_width(c)).find(|&w| w > 0);
+                    let reserved = usize::from(hyphenate && c_width == 1 && next_width == Some(1));
+                    if width + c_width + reserved > columns {
+                        let last_width = line.chars().rev().map(char_width).find(|&w| w > 0);
+                        if hyphenate && c_width == 1 && last_width == Some(1) && width < columns {
+                            line.push('-');
+                        }
+                        lines.push(std::mem::take(&mut line));
+                        width = 0;
+                    }
+                }
+                line.push(c);
+                width += c_width;
+                remaining -= c_width;
+            }
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_width() {
+        assert_eq!(char_width('a'), 1);
+        assert_eq!(char_width('é'), 1);
+        assert_eq!(char_width('ｱ'), 1);
+        assert_eq!(char_width('日'), 2);
+        assert_eq!(char_width('한'), 2);
+        assert_eq!(char_width('Ａ'), 2);
+        assert_eq!(char_width('\u{0301}'), 0);
+        assert_eq!(char_width('\n'), 0);
+    }
+
+    #[test]
+    fn test_text_width() {
+        assert_eq!(text_width(""), 0);
+        assert_eq!(text_width("Crème brûlée"), 12);
+        assert_eq!(text_width("北京烤鸭 x2"), 11);
+        assert_eq!(text_width("김치찌개"), 8);
+
+        // Combining marks take no column
+        assert_eq!(text_width("ที่นี่"), 2);
+        assert_eq!(text_width("Vie\u{0323}\u{0302}t"), 4);
+    }
+
+    #[test]
+    fn test_wrap_text() {
+        assert_eq!(wrap_text("", 10, false), Vec::<String>::new());
+        assert_eq!(
+            wrap_text("The quick  brown fox\n\njumps over", 10, false),
+            vec!["The quick", "brown fox", "", "jumps over"]
+        );
+        assert_eq!(
+            wrap_text("a verylongword b", 6, false),
+            vec!["a", "verylo", "ngword", "b"]
+        );
+        assert_eq!(
+            wrap_text("a verylongword b", 6, true),
+            vec!["a", "veryl-", "ongwo-", "rd b"]
+        );
+
+        // Wide characters take two columns and are not hyphenated
+        assert_eq!(wrap_text("北京烤鸭 x2", 8, true), vec!["北京烤鸭", "x2"]);
+        assert_eq!(wrap_text("北京烤鸭北京烤鸭", 7, true), vec!["北京烤", "鸭北京", "烤鸭"]);
+        assert_eq!(wrap_text("北京烤abc", 7, true), vec!["北京烤", "abc"]);
+        assert_eq!(wrap_text("北京烤鸭abcdef", 9, true), vec!["北京烤鸭", "abcdef"]);
+        assert_eq!(
+            wrap_text("北京烤鸭abcdefghijk", 9, true),
+            vec!["北京烤鸭", "abcdefgh-", "ijk"]
+        );
+        assert!(wrap_text("北京烤鸭abcdefghijk北京", 9, true)
+            .iter()
+            .all(|line| text_width(line) <= 9));
+
+        // Combining marks stay with their base character
+        assert_eq!(
+            wrap_text("Vie\u{0323}\u{0302}tnam", 4, false),
+            vec!["Vie\u{0323}\u{0302}t", "nam"]
+        );
+        assert_eq!(wrap_text("abc", 1, true), vec!["a", "b", "c"]);
+    }
+}
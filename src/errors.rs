@@ -2,7 +2,12 @@
 
 #[cfg(feature = "graphics")]
 use image::ImageError;
-use std::{borrow::Cow, cell::BorrowMutError, fmt, io, num::TryFromIntError};
+use std::{
+    borrow::Cow,
+    cell::{BorrowError, BorrowMutError},
+    fmt, io,
+    num::TryFromIntError,
+};
 
 /// Custom Result for `PrinterError`
 pub type Result<T> = std::result::Result<T, PrinterError>;
@@ -13,6 +18,7 @@ pub enum PrinterError {
     Io(String),
     Input(String),
     InvalidResponse(String),
+    Unsupported(String),
 }
 
 impl std::error::Error for PrinterError {}
@@ -23,6 +29,7 @@ impl fmt::Display for PrinterError {
             PrinterError::Io(ref err) => write!(f, "IO error: {err}"),
             PrinterError::Input(ref err) => write!(f, "Input error: {err}"),
             PrinterError::InvalidResponse(ref err) => write!(f, "Invalid response: {err}"),
+            PrinterError::Unsupported(ref err) => write!(f, "Unsupported instruction: {err}"),
         }
     }
 }
@@ -39,6 +46,12 @@ impl From<Cow<'_, str>> for PrinterError {
     }
 }
 
+impl From<BorrowError> for PrinterError {
+    fn from(err: BorrowError) -> Self {
+        PrinterError::Io(err.to_string())
+    }
+}
+
 impl From<BorrowMutError> for PrinterError {
     fn from(err: BorrowMutError) -> Self {
         PrinterError::Io(err.to_string())
@@ -1,10 +1,14 @@
 //! Drivers used to send data to the printer (Network or USB)
 
+#[cfg(feature = "graphics")]
+use crate::domain::{RenderOption, Renderer};
 use crate::errors::{PrinterError, Result};
 #[cfg(feature = "native_usb")]
 use futures_lite::future::block_on;
 #[cfg(feature = "hidapi")]
 use hidapi::{HidApi, HidDevice};
+#[cfg(feature = "graphics")]
+use image::GrayImage;
 #[cfg(feature = "native_usb")]
 use nusb::transfer::RequestBuffer;
 #[cfg(feature = "usb")]
@@ -611,3 +615,83 @@ impl Driver for SerialPortDriver {
         Ok(self.port.try_borrow_mut()?.flush()?)
     }
 }
+
+// ================ Render driver ================
+
+/// Driver rendering the printed data into an image instead of sending it to a printer
+#[cfg(feature = "graphics")]
+#[derive(Clone)]
+pub struct RenderDriver {
+    renderer: Renderer,
+    buffer: Rc<RefCell<Vec<u8>>>,
+}
+
+#[cfg(feature = "graphics")]
+impl RenderDriver {
+    /// Open the render driver
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    ///
+    /// let driver = RenderDriver::open(RenderOption::default());
+    /// Printer::new(driver.clone(), Protocol::default(), None)
+    ///     .init()
+    ///     .unwrap()
+    ///     .writeln("Preview")
+    ///     .unwrap()
+    ///     .print_cut()
+    ///     .unwrap();
+    /// driver.save("./receipt.png").unwrap();
+    /// ```
+    pub fn open(option: RenderOption) -> Self {
+        Self {
+            renderer: Renderer::new(option),
+            buffer: Rc::new(RefCell::new(vec![])),
+        }
+    }
+
+    /// Get the data received by the driver
+    pub fn data(&self) -> Result<Vec<u8>> {
+        Ok(self.buffer.try_borrow()?.clone())
+    }
+
+    /// Clear the data received by the driver
+    pub fn clear(&self) -> Result<()> {
+        self.buffer.try_borrow_mut()?.clear();
+        Ok(())
+    }
+
+    /// Render the data received by the driver
+    pub fn render(&self) -> Result<GrayImage> {
+        Ok(self.renderer.render(&self.buffer.try_borrow()?))
+    }
+
+    /// Render the data received by the driver and save it as an image (e.g. PNG)
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        Ok(self.render()?.save(path)?)
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl Driver for RenderDriver {
+    fn name(&self) -> String {
+        "render".to_owned()
+    }
+
+    fn write(&self, data: &[u8]) -> Result<()> {
+        self.buffer.try_borrow_mut()?.extend_from_slice(data);
+        Ok(())
+    }
+
+    fn read(&self, _buf: &mut [u8]) -> Result<usize> {
+        Ok(0)
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+}
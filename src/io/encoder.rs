@@ -1,34 +1,150 @@
 //! Encoder used to encode text
 
-use crate::errors::Result;
-use encoding_rs::{Encoding, UTF_8};
+use crate::errors::{PrinterError, Result};
+use encoding_rs::{EncoderResult, Encoding, UTF_8};
+
+/// Default replacement character for the characters which cannot be encoded
+const DEFAULT_REPLACEMENT: char = '?';
 
 /// Encoder
+///
+/// Encode the text with an `encoding_rs` codec, including legacy single and multi-byte encodings
+/// (`WINDOWS_1252`, `SHIFT_JIS`, `GBK`...). Characters which cannot be encoded are replaced
+/// (`?` by default) or reported as an error.
+///
+/// # Examples
+///
+/// ```rust
+/// use escpos::printer::Printer;
+/// use escpos::utils::*;
+/// use escpos::{driver::*, errors::Result};
+/// use encoding_rs::WINDOWS_1252;
+///
+/// fn main() -> Result<()> {
+///     let driver = ConsoleDriver::open(false);
+///     let encoder = Encoder::new(WINDOWS_1252).with_replacement(None);
+///
+///     // Without page code, the text is encoded by the encoder
+///     Printer::new(driver, Protocol::new(encoder), None)
+///         .init()?
+///         .writeln("Crème brûlée 5 €")?
+///         .print_cut()?;
+///
+///     Ok(())
+/// }
+/// ```
 #[derive(Clone)]
 pub struct Encoder {
     codec: &'static Encoding,
+    replacement: Option<char>,
 }
 
 impl Default for Encoder {
     fn default() -> Self {
-        Encoder { codec: UTF_8 }
+        Encoder::new(UTF_8)
     }
 }
 
 impl Encoder {
     /// Create a new encoder
     pub fn new(codec: &'static Encoding) -> Self {
-        Self { codec }
+        Self {
+            codec,
+            replacement: Some(DEFAULT_REPLACEMENT),
+        }
+    }
+
+    /// Set the replacement of the characters which cannot be encoded (`None` to return an error)
+    pub fn with_replacement(mut self, replacement: Option<char>) -> Self {
+        self.replacement = replacement;
+        self
+    }
+
+    /// Get codec
+    pub fn codec(&self) -> &'static Encoding {
+        self.codec
     }
 
     /// Encode string into the right codec
     pub(crate) fn encode(&self, data: &str) -> Result<Vec<u8>> {
-        match self.codec.can_encode_everything() {
-            true => Ok(self.codec.encode(data).0.into()),
-            false => Err(crate::errors::PrinterError::Input(format!(
-                "invalid {}",
-                self.codec.name()
+        let mut encoder = self.codec.new_encoder();
+        let mut output = Vec::with_capacity(data.len());
+        let mut input = data;
+        let mut unmappable = Vec::new();
+
+        loop {
+            let (result, read) = encoder.encode_from_utf8_to_vec_without_replacement(input, &mut output, true);
+            input = &input[read..];
+
+            match result {
+                EncoderResult::InputEmpty => break,
+                EncoderResult::OutputFull => output.reserve(input.len().max(16)),
+                EncoderResult::Unmappable(c) => match self.replacement {
+                    Some(replacement) => {
+                        let mut buffer = [0; 4];
+                        output.reserve(16);
+
+                        // An unmappable replacement is ignored
+                        let _ = encoder.encode_from_utf8_to_vec_without_replacement(
+                            replacement.encode_utf8(&mut buffer),
+                            &mut output,
+                            false,
+                        );
+                    }
+                    None => {
+                        let position = data[..data.len() - input.len()].chars().count() - 1;
+                        unmappable.push(format!("{c:?} at {position}"));
+                    }
+                },
+            }
+        }
+
+        match unmappable.is_empty() {
+            true => Ok(output),
+            false => Err(PrinterError::Input(format!(
+                "characters which cannot be encoded in {}: {}",
+                self.codec.name(),
+                unmappable.join(", ")
             ))),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding_rs::{ISO_2022_JP, SHIFT_JIS, WINDOWS_1252};
+
+    #[test]
+    fn test_encode_utf8() {
+        let encoder = Encoder::default();
+        assert_eq!(encoder.encode("é ✓").unwrap(), "é ✓".as_bytes());
+    }
+
+    #[test]
+    fn test_encode_single_byte() {
+        let encoder = Encoder::new(WINDOWS_1252);
+        assert_eq!(encoder.encode("Crème 5 €").unwrap(), b"Cr\xE8me 5 \x80");
+        assert_eq!(encoder.encode("Борщ ✓").unwrap(), b"???? ?");
+
+        let encoder = encoder.with_replacement(Some('_'));
+        assert_eq!(encoder.encode("a✓b").unwrap(), b"a_b");
+
+        let encoder = encoder.with_replacement(None);
+        assert_eq!(
+            encoder.encode("a✓b€Б").unwrap_err().to_string(),
+            "Input error: characters which cannot be encoded in windows-1252: '✓' at 1, 'Б' at 4"
+        );
+    }
+
+    #[test]
+    fn test_encode_multi_byte() {
+        let encoder = Encoder::new(SHIFT_JIS);
+        assert_eq!(encoder.encode("aｱ日本").unwrap(), b"a\xB1\x93\xFA\x96\x7B");
+        assert_eq!(encoder.encode("한").unwrap(), b"?");
+
+        // Stateful encoding: the replacement is encoded in the current state
+        let encoder = Encoder::new(ISO_2022_JP);
+        assert_eq!(encoder.encode("日한本").unwrap(), b"\x1B$BF|\x1B(B?\x1B$BK\\\x1B(B");
+    }
+}
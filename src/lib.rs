@@ -109,6 +109,9 @@
 //! use escpos::utils::*;
 //! use escpos::{driver::*, errors::Result};
 //!
+//! # #[cfg(not(feature = "graphics"))]
+//! # fn main() {}
+//! # #[cfg(feature = "graphics")]
 //! fn main() -> Result<()> {
 //!     // env_logger::init();
 //!
@@ -165,11 +168,12 @@
 //! | ------------- | ---------------------------------------------------------------------- | :-----: |
 //! | `barcodes`    | Print barcodes (UPC-A, UPC-E, EAN8, EAN13, CODE39, ITF or CODABAR)     |   ✅    |
 //! | `codes_2d`    | Print 2D codes (QR Code, PDF417, GS1 DataBar, DataMatrix, Aztec, etc.) |   ✅    |
-//! | `graphics`    | Print raster images                                                    |   ❌    |
+//! | `graphics`    | Print raster images and render previews (`RenderDriver`)               |   ❌    |
 //! | `usb`         | Enable USB feature                                                     |   ❌    |
 //! | `native_usb`  | Enable native USB feature                                              |   ❌    |
 //! | `hidapi`      | Enable HidApi feature                                                  |   ❌    |
 //! | `serial_port` | Enable Serial port feature                                             |   ❌    |
+//! | `serde`       | Load printer profiles from JSON or TOML                                |   ❌    |
 //! | `full`        | Enable all features                                                    |   ❌    |
 //!
 //! ## External resources
@@ -1,7 +1,11 @@
 //! Printer
 
-use super::errors::Result;
-use crate::{domain::*, driver::Driver, utils::Protocol};
+use super::errors::{PrinterError, Result};
+use crate::{
+    domain::{indic::IndicScript, *},
+    driver::Driver,
+    utils::Protocol,
+};
 use log::debug;
 
 /// Printer
@@ -33,8 +37,42 @@ pub struct Printer<D: Driver> {
     page_code: Option<PageCode>,
     instructions: Vec<Instruction>,
     debug_mode: Option<DebugMode>,
+    profile: Option<PrinterProfile>,
+    capability_policy: CapabilityPolicy,
+    auto_page_code: bool,
+    kanji_encoding: Option<KanjiEncoding>,
+    indic_script: Option<IndicScript>,
+    unicode_mode: bool,
+    bidi_mode: bool,
+    justified: bool,
+    state: PrintState,
+    styles: Vec<(Style, bool)>,
+    page_mode: Option<PageMode>,
 }
 
+/// Page mode settings (print area in dots and direction)
+#[derive(Debug, Default, Clone, Copy)]
+struct PageMode {
+    area: Option<(u16, u16, u16, u16)>,
+    direction: PageDirection,
+}
+
+/// Font A metrics used without profile (80 mm paper)
+const DEFAULT_FONT_A: FontProfile = FontProfile {
+    font: Font::A,
+    width: 12,
+    height: 24,
+    columns: 48,
+};
+
+/// Font B metrics used without profile (80 mm paper)
+const DEFAULT_FONT_B: FontProfile = FontProfile {
+    font: Font::B,
+    width: 9,
+    height: 17,
+    columns: 64,
+};
+
 impl<D: Driver> Printer<D> {
     /// Create a new `Printer`
     ///
@@ -57,6 +95,17 @@ impl<D: Driver> Printer<D> {
             page_code,
             instructions: vec![],
             debug_mode: None,
+            profile: None,
+            capability_policy: CapabilityPolicy::default(),
+            auto_page_code: false,
+            kanji_encoding: None,
+            indic_script: None,
+            unicode_mode: false,
+            bidi_mode: false,
+            justified: false,
+            state: PrintState::default(),
+            styles: vec![],
+            page_mode: None,
         }
     }
 
@@ -77,6 +126,308 @@ impl<D: Driver> Printer<D> {
         self
     }
 
+    /// Set printer profile
+    ///
+    /// The profile provides the ESC t numbers of the page codes and the instructions are checked against it
+    /// (see [`Printer::capability_policy`]).
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     Printer::new(driver, Protocol::default(), Some(PageCode::PC858))
+    ///         .profile(Some(PrinterProfile::from(PrinterModel::EpsonTmT20)))
+    ///         .init()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn profile(&mut self, profile: Option<PrinterProfile>) -> &mut Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Set the behavior when an instruction is not supported by the profile
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     let mut printer = Printer::new(driver, Protocol::default(), None);
+    ///     printer.profile(Some(PrinterProfile::from(PrinterModel::XprinterXp58)));
+    ///
+    ///     // Font C and the partial cut are not available on this model
+    ///     assert!(printer.font(Font::C).is_err());
+    ///
+    ///     printer
+    ///         .capability_policy(CapabilityPolicy::Degrade)
+    ///         .font(Font::C)?
+    ///         .partial_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn capability_policy(&mut self, policy: CapabilityPolicy) -> &mut Self {
+        self.capability_policy = policy;
+        self
+    }
+
+    /// Enable or disable automatic page code switching
+    ///
+    /// When enabled, `write` selects for each run of characters missing from the current page code
+    /// a page code of the profile (or any page code without profile) which contains them,
+    /// and restores the current page code afterwards.
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     Printer::new(driver, Protocol::default(), Some(PageCode::PC858))
+    ///         .auto_page_code(true)
+    ///         .init()?
+    ///         .writeln("Crème brûlée, Σουβλάκι, Борщ")?
+    ///         .print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn auto_page_code(&mut self, enabled: bool) -> &mut Self {
+        self.auto_page_code = enabled;
+        self
+    }
+
+    /// Set the behavior when a character is missing from the page code table
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     let mut printer = Printer::new(driver, Protocol::default(), Some(PageCode::PC437));
+    ///     printer.unmappable_policy(UnmappablePolicy::Strict);
+    ///     assert!(printer.write("Total: 10 €").is_err());
+    ///
+    ///     printer
+    ///         .unmappable_policy(UnmappablePolicy::Transliterate)
+    ///         .writeln("Total: 10 €")? // "Total: 10 EUR"
+    ///         .print()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn unmappable_policy(&mut self, policy: UnmappablePolicy) -> &mut Self {
+        self.protocol.set_unmappable_policy(policy);
+        self
+    }
+
+    /// Set the behavior for the control characters of the text
+    ///
+    /// By default, the control characters (except LF and HT) are stripped from the text so that
+    /// untrusted text cannot send commands. Use `Printer::custom` to send trusted commands.
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     Printer::new(driver, Protocol::default(), None)
+    ///         .control_character_policy(ControlCharacterPolicy::Escape)
+    ///         .writeln("Name: \x1Bp\x00")? // "Name: ^[p^@"
+    ///         .print()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn control_character_policy(&mut self, policy: ControlCharacterPolicy) -> &mut Self {
+        self.protocol.set_control_character_policy(policy);
+        self
+    }
+
+    /// Set the control characters allowed in the text (LF and HT by default)
+    pub fn allowed_control_characters(&mut self, characters: &[char]) -> &mut Self {
+        self.protocol.set_allowed_control_characters(characters);
+        self
+    }
+
+    /// Set the bidirectional text mode
+    ///
+    /// When enabled, each line written is shaped (Arabic presentation forms available in the page code)
+    /// and reordered with the Unicode bidirectional algorithm. Right-to-left lines ended by a line feed are
+    /// right-aligned unless a justification has been selected since the initialization.
+    /// A line must be written at once to be reordered correctly.
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     Printer::new(driver, Protocol::default(), Some(PageCode::PC864))
+    ///         .bidi_mode(true)
+    ///         .init()?
+    ///         .writeln("مرحبا")?
+    ///         .writeln("المجموع: 25.00")?
+    ///         .print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn bidi_mode(&mut self, enabled: bool) -> &mut Self {
+        self.bidi_mode = enabled;
+        self
+    }
+
+    /// Page codes available for automatic switching with their ESC t number
+    ///
+    /// The code pages registered on the protocol are always available, with their own ESC t number.
+    fn available_page_codes(&self) -> Vec<(PageCode, u8)> {
+        let page_codes =
+            match &self.profile {
+                Some(profile) => profile
+                    .page_codes()
+                    .iter()
+                    .map(|page_code| page_code.page_code)
+                    .chain(PageCode::all().into_iter().filter(|&code| {
+                        !profile.has_page_code(code) && self.protocol.registered_code_page(code).is_some()
+                    }))
+                    .collect(),
+                None => PageCode::all(),
+            };
+
+        page_codes
+            .into_iter()
+            .filter_map(|code| Some((code, self.page_code_number(code)?)))
+            .collect()
+    }
+
+    /// ESC t number of a page code: the registered code page one, else the profile one (if any)
+    fn page_code_number(&self, code: PageCode) -> Option<u8> {
+        match (self.protocol.registered_code_page(code), &self.profile) {
+            (Some(code_page), _) => Some(code_page.number()),
+            (None, Some(profile)) => profile.page_code_number(code),
+            (None, None) => Some(code.into()),
+        }
+    }
+
+    /// Check if the profile (if any) supports a capability
+    fn supports(&self, capability: Capability) -> bool {
+        match &self.profile {
+            Some(profile) => profile.has_capability(capability),
+            None => true,
+        }
+    }
+
+    /// Error for an instruction not supported by the profile
+    fn unsupported(&self, instruction: &str) -> PrinterError {
+        let name = self.profile.as_ref().map(|profile| profile.name()).unwrap_or_default();
+        PrinterError::Unsupported(format!("{instruction} is not supported by {name}"))
+    }
+
+    /// Check that the profile supports a capability which has no fallback
+    fn require(&self, capability: Capability) -> Result<()> {
+        match self.supports(capability) {
+            true => Ok(()),
+            false => Err(self.unsupported(&capability.to_string())),
+        }
+    }
+
+    /// Check if an unsupported instruction can be replaced by a fallback
+    fn degrade(&self, instruction: &str, fallback: &str) -> Result<()> {
+        match self.capability_policy {
+            CapabilityPolicy::Strict => Err(self.unsupported(instruction)),
+            CapabilityPolicy::Degrade => {
+                if self.debug_mode.is_some() {
+                    debug!("[degrade] {instruction} replaced by {fallback}");
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Character page code command using the registered code page or profile ESC t number if any
+    fn page_code_command(&self, code: PageCode) -> Result<Command> {
+        match (self.protocol.registered_code_page(code), &self.profile) {
+            (None, Some(profile)) => match profile.page_code_number(code) {
+                Some(number) => Ok(self.protocol.page_code_number(number)),
+                None => Err(self.unsupported(&format!("page code {code}"))),
+            },
+            _ => Ok(self.protocol.page_code(code)),
+        }
+    }
+
+    /// Metrics of the current font (from the profile, or the ones of a 80 mm printer without profile)
+    fn font_profile(&self) -> FontProfile {
+        let font = self.state.font.unwrap_or(Font::A);
+        self.profile
+            .as_ref()
+            .and_then(|profile| profile.font(font).copied())
+            .unwrap_or(match font {
+                Font::A => DEFAULT_FONT_A,
+                Font::B | Font::C => FontProfile { font, ..DEFAULT_FONT_B },
+            })
+    }
+
+    /// Font supported by the profile
+    fn supported_font(&self, font: Font) -> Result<Font> {
+        match &self.profile {
+            Some(profile) if profile.font(font).is_none() => match profile.fonts().first() {
+                Some(fallback) => {
+                    self.degrade(&font.to_string(), &fallback.font.to_string())?;
+                    Ok(fallback.font)
+                }
+                None => Err(self.unsupported(&font.to_string())),
+            },
+            _ => Ok(font),
+        }
+    }
+
+    /// Cut supported by the profile (`None` if the printer has no cutter)
+    fn supported_cut(&self, partial: bool) -> Result<Option<bool>> {
+        let (capability, fallback) = match partial {
+            true => (Capability::PartialCut, Capability::FullCut),
+            false => (Capability::FullCut, Capability::PartialCut),
+        };
+
+        if self.supports(capability) {
+            Ok(Some(partial))
+        } else if self.supports(fallback) {
+            self.degrade(&capability.to_string(), &fallback.to_string())?;
+            Ok(Some(!partial))
+        } else {
+            self.degrade(&capability.to_string(), "nothing (no cutter)")?;
+            Ok(None)
+        }
+    }
+
+    /// Paper cut
+    fn cut_paper(&mut self, partial: bool) -> Result<&mut Self> {
+        match self.supported_cut(partial)? {
+            Some(true) => {
+                let cmd = self.protocol.cut(true);
+                self.command("partial paper cut", &[cmd])
+            }
+            Some(false) => {
+                let cmd = self.protocol.cut(false);
+                self.command("full paper cut", &[cmd])
+            }
+            None => Ok(self),
+        }
+    }
+
     /// Display logs of instructions if debug mode is enabled
     pub fn debug(&mut self) -> Result<&mut Self> {
         if self.debug_mode.is_some() {
@@ -114,66 +465,183 @@ impl<D: Driver> Printer<D> {
     }
 
     /// Hardware initialization
+    ///
+    /// The style stack is cleared, the settings after initialization not having to be restored.
     pub fn init(&mut self) -> Result<&mut Self> {
         let cmd = self.protocol.init();
         self.command("initialization", &[cmd])?;
+        self.justified = false;
+        self.indic_script = None;
+        self.state = PrintState::initialized();
+        self.styles.clear();
+        self.state.print_area_width = self.profile.as_ref().map(|profile| profile.dots_per_line());
+        self.page_mode = None;
+        self.protocol.set_character_set(CharacterSet::default());
 
         // Set page code
         if let Some(page_code) = self.page_code {
-            let cmd = self.protocol.page_code(page_code);
-            self.command("character page code", &[cmd])?;
+            self.page_code(page_code)?;
         }
 
         Ok(self)
     }
 
     /// Hardware reset
+    ///
+    /// The print state is kept, `Printer::restore_state` sends it again.
     pub fn reset(&mut self) -> Result<&mut Self> {
         let cmd = self.protocol.reset();
         self.command("reset", &[cmd])
     }
 
+    /// Print state (settings sent to the printer)
+    pub fn state(&self) -> &PrintState {
+        &self.state
+    }
+
+    /// Send the known settings of the print state again (after a hardware reset or a reconnection)
+    ///
+    /// If a setting cannot be sent, the print state is kept unchanged.
+    pub fn restore_state(&mut self) -> Result<&mut Self> {
+        let state = self.state.clone();
+        let justified = self.justified;
+
+        // Settings equal to the tracked ones are skipped, so all of them are sent from an unknown state
+        self.state = PrintState::default();
+        let result = self.send_state(&state);
+        self.justified = justified;
+        if let Err(e) = result {
+            self.state = state;
+            return Err(e);
+        }
+
+        Ok(self)
+    }
+
+    /// Send the known settings of a print state
+    fn send_state(&mut self, state: &PrintState) -> Result<()> {
+        if let Some(font) = state.font {
+            self.font(font)?;
+        }
+        if let Some(enabled) = state.bold {
+            self.bold(enabled)?;
+        }
+        if let Some(mode) = state.underline {
+            self.underline(mode)?;
+        }
+        if let Some((width, height)) = state.size {
+            self.size(width, height)?;
+        }
+        if let Some(mode) = state.justify {
+            self.justify(mode)?;
+        }
+        if let Some(enabled) = state.reverse {
+            self.reverse(enabled)?;
+        }
+        match state.line_spacing {
+            Some(LineSpacing::Default) => self.reset_line_spacing()?,
+            Some(LineSpacing::Units(value)) => self.line_spacing(value)?,
+            None => self,
+        };
+        if let Some((x, y)) = state.motion_units {
+            self.motion_units(x, y)?;
+        }
+        if let Some(margin) = state.left_margin {
+            self.left_margin(margin)?;
+        }
+        if let Some(width) = state.print_area_width {
+            self.print_area_width(width)?;
+        }
+        if let Some(value) = state.character_spacing {
+            self.character_spacing(value)?;
+        }
+        if let Some(code) = state.character_set {
+            self.character_set(code)?;
+        }
+        if let Some(code) = state.page_code {
+            self.page_code(code)?;
+        }
+
+        Ok(())
+    }
+
     /// Paper full cut
     pub fn cut(&mut self) -> Result<&mut Self> {
-        let cmd = self.protocol.cut(false);
-        self.command("full paper cut", &[cmd])
+        self.cut_paper(false)
     }
 
     /// Paper partial cut
     pub fn partial_cut(&mut self) -> Result<&mut Self> {
-        let cmd = self.protocol.cut(true);
-        self.command("partial paper cut", &[cmd])
+        self.cut_paper(true)
     }
 
     /// Print and paper full cut
     pub fn print_cut(&mut self) -> Result<&mut Self> {
-        let cmd = self.protocol.cut(false);
-        self.command("full paper cut", &[cmd])?.print()
+        self.cut_paper(false)?.print()
     }
 
     /// Character page code
     pub fn page_code(&mut self, code: PageCode) -> Result<&mut Self> {
+        let cmd = self.page_code_command(code)?;
         self.page_code = Some(code);
+        if self.state.page_code == Some(code) {
+            return Ok(self);
+        }
+        self.state.page_code = Some(code);
 
-        let cmd = self.protocol.page_code(code);
         self.command("character page code", &[cmd])
     }
 
     /// International character set
+    ///
+    /// The characters of the character set are encoded at the ASCII positions they replace (`ä` for
+    /// `CharacterSet::Germany`), and the replaced ASCII characters are treated as missing from the page code.
+    /// With an India character set (`CharacterSet::India*`), the text is encoded in ISCII.
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     Printer::new(driver, Protocol::default(), None)
+    ///         .init()?
+    ///         .character_set(CharacterSet::IndiaDevanagari)?
+    ///         .writeln("नमस्ते")?
+    ///         .print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
     pub fn character_set(&mut self, code: CharacterSet) -> Result<&mut Self> {
+        self.indic_script = IndicScript::from_character_set(&code);
+        self.protocol.set_character_set(code);
+        if self.state.character_set == Some(code) {
+            return Ok(self);
+        }
         let cmd = self.protocol.character_set(code);
+        self.state.character_set = Some(code);
         self.command("international character set", &[cmd])
     }
 
     /// Text bold
     pub fn bold(&mut self, enabled: bool) -> Result<&mut Self> {
+        if self.state.bold == Some(enabled) {
+            return Ok(self);
+        }
         let cmd = self.protocol.bold(enabled);
+        self.state.bold = Some(enabled);
         self.command("text bold", &[cmd])
     }
 
     /// Text underline
     pub fn underline(&mut self, mode: UnderlineMode) -> Result<&mut Self> {
+        if self.state.underline == Some(mode) {
+            return Ok(self);
+        }
         let cmd = self.protocol.underline(mode);
+        self.state.underline = Some(mode);
         self.command("text underline", &[cmd])
     }
 
@@ -185,7 +653,12 @@ impl<D: Driver> Printer<D> {
 
     /// Text font
     pub fn font(&mut self, font: Font) -> Result<&mut Self> {
+        let font = self.supported_font(font)?;
+        if self.state.font == Some(font) {
+            return Ok(self);
+        }
         let cmd = self.protocol.font(font);
+        self.state.font = Some(font);
         self.command("text font", &[cmd])
     }
 
@@ -197,26 +670,216 @@ impl<D: Driver> Printer<D> {
 
     /// Text justify
     pub fn justify(&mut self, mode: JustifyMode) -> Result<&mut Self> {
+        self.justified = true;
+        if self.state.justify == Some(mode) {
+            return Ok(self);
+        }
         let cmd = self.protocol.justify(mode);
+        self.state.justify = Some(mode);
         self.command("text justify", &[cmd])
     }
 
     /// Text reverse colour
     pub fn reverse(&mut self, enabled: bool) -> Result<&mut Self> {
+        if self.state.reverse == Some(enabled) {
+            return Ok(self);
+        }
         let cmd = self.protocol.reverse_colours(enabled);
+        self.state.reverse = Some(enabled);
         self.command("text reverse colour", &[cmd])
     }
 
     /// Text size
     pub fn size(&mut self, width: u8, height: u8) -> Result<&mut Self> {
         let cmd = self.protocol.text_size(width, height)?;
+        if self.state.size == Some((width, height)) {
+            return Ok(self);
+        }
+        self.state.size = Some((width, height));
         self.command("text size", &[cmd])
     }
 
     /// Reset text size
     pub fn reset_size(&mut self) -> Result<&mut Self> {
-        let cmd = self.protocol.text_size(1, 1)?;
-        self.command("text size", &[cmd])
+        self.size(1, 1)
+    }
+
+    /// Text print mode (`ESC !`), setting the font, bold, double height and width and underline at once
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     let mut printer = Printer::new(driver, Protocol::default(), None);
+    ///     printer
+    ///         .init()?
+    ///         .print_mode(PrintMode::new().with_bold(true).with_double_width(true))?
+    ///         .writeln("Total")?
+    ///         .print_mode(PrintMode::new())?
+    ///         .character_spacing(2)?
+    ///         .writeln("Thank you")?
+    ///         .print_cut()?;
+    ///     assert_eq!(printer.state().size(), Some((1, 1)));
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn print_mode(&mut self, mode: PrintMode) -> Result<&mut Self> {
+        let mode = mode.with_font(self.supported_font(mode.font)?);
+        let cmd = self.protocol.print_mode(mode)?;
+        self.state.font = Some(mode.font);
+        self.state.bold = Some(mode.bold);
+        self.state.underline = Some(match mode.underline {
+            true => UnderlineMode::Single,
+            false => UnderlineMode::None,
+        });
+        self.state.size = Some(mode.size());
+        self.command("text print mode", &[cmd])
+    }
+
+    /// Right-side character spacing (`ESC SP`), in horizontal motion units (see `Printer::motion_units`)
+    ///
+    /// The spacing is doubled with double-width characters, and taken into account by `Printer::columns`.
+    pub fn character_spacing(&mut self, value: u8) -> Result<&mut Self> {
+        if self.state.character_spacing == Some(value) {
+            return Ok(self);
+        }
+        let cmd = self.protocol.character_spacing(value);
+        self.state.character_spacing = Some(value);
+        self.command("character spacing", &[cmd])
+    }
+
+    /// Unidirectional printing (`ESC U`), improving the alignment of impact printers
+    pub fn unidirectional(&mut self, enabled: bool) -> Result<&mut Self> {
+        let cmd = self.protocol.unidirectional(enabled);
+        self.command("unidirectional printing", &[cmd])
+    }
+
+    /// Character colour (`GS ( N`), on multi-colour printers
+    pub fn character_colour(&mut self, colour: TextColour) -> Result<&mut Self> {
+        let cmd = self.protocol.character_colour(colour);
+        self.command("character colour", &[cmd])
+    }
+
+    /// Background colour (`GS ( N`), on multi-colour printers
+    pub fn background_colour(&mut self, colour: TextColour) -> Result<&mut Self> {
+        let cmd = self.protocol.background_colour(colour);
+        self.command("background colour", &[cmd])
+    }
+
+    /// Character shading (`GS ( N`), in a colour on multi-colour printers
+    pub fn shading(&mut self, enabled: bool, colour: TextColour) -> Result<&mut Self> {
+        let cmd = self.protocol.shading(enabled, colour);
+        self.command("character shading", &[cmd])
+    }
+
+    /// Apply the settings of a style which differ from the current ones and get the style restoring them
+    ///
+    /// The unknown settings are restored to their value after initialization. If a setting cannot be applied,
+    /// the settings already sent are restored before returning the error.
+    fn apply_style(&mut self, style: &Style) -> Result<Style> {
+        let mut restore = Style::new();
+        match self.set_style(style, &mut restore) {
+            Ok(()) => Ok(restore),
+            Err(e) => {
+                // The error of the setting takes precedence over the one of the restoration
+                let _ = self.set_style(&restore, &mut Style::new());
+                Err(e)
+            }
+        }
+    }
+
+    /// Send the settings of a style which differ from the current ones, recording the previous ones in `restore`
+    fn set_style(&mut self, style: &Style, restore: &mut Style) -> Result<()> {
+        if let Some(enabled) = style.bold.filter(|&enabled| Some(enabled) != self.state.bold) {
+            restore.bold = Some(self.state.bold.unwrap_or(false));
+            self.bold(enabled)?;
+        }
+        if let Some(mode) = style.underline.filter(|&mode| Some(mode) != self.state.underline) {
+            restore.underline = Some(self.state.underline.unwrap_or(UnderlineMode::None));
+            self.underline(mode)?;
+        }
+        if let Some(font) = style.font.filter(|&font| Some(font) != self.state.font) {
+            restore.font = Some(self.state.font.unwrap_or(Font::A));
+            self.font(font)?;
+        }
+        if let Some((width, height)) = style.size.filter(|&size| Some(size) != self.state.size) {
+            restore.size = Some(self.state.size.unwrap_or((1, 1)));
+            self.size(width, height)?;
+        }
+        if let Some(mode) = style.justify.filter(|&mode| Some(mode) != self.state.justify) {
+            restore.justify = Some(self.state.justify.unwrap_or(JustifyMode::LEFT));
+            self.justify(mode)?;
+        }
+        if let Some(enabled) = style.reverse.filter(|&enabled| Some(enabled) != self.state.reverse) {
+            restore.reverse = Some(self.state.reverse.unwrap_or(false));
+            self.reverse(enabled)?;
+        }
+
+        Ok(())
+    }
+
+    /// Push a style on the style stack
+    ///
+    /// Only the settings which differ from the current ones are sent, and restored by `Printer::pop_style`.
+    pub fn push_style(&mut self, style: Style) -> Result<&mut Self> {
+        let justified = self.justified;
+        let restore = self.apply_style(&style)?;
+        self.styles.push((restore, justified));
+        Ok(self)
+    }
+
+    /// Pop the last style pushed on the style stack, restoring the previous settings
+    pub fn pop_style(&mut self) -> Result<&mut Self> {
+        match self.styles.pop() {
+            Some((restore, justified)) => {
+                let result = self.apply_style(&restore);
+                self.justified = justified;
+                result?;
+                Ok(self)
+            }
+            None => Err(PrinterError::Input("no style to pop".to_owned())),
+        }
+    }
+
+    /// Style applied to the instructions of a closure
+    ///
+    /// The previous settings are restored after the closure, even if it returns an error.
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     Printer::new(driver, Protocol::default(), None)
+    ///         .init()?
+    ///         .with_style(Style::new().with_bold(true).with_size(2, 2), |printer| {
+    ///             printer
+    ///                 .writeln("My Shop")?
+    ///                 .with_style(Style::new().with_underline(UnderlineMode::Single), |printer| {
+    ///                     printer.writeln("Open every day")
+    ///                 })
+    ///         })?
+    ///         .writeln("1, rue des gloutons")?
+    ///         .print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_style<F>(&mut self, style: Style, f: F) -> Result<&mut Self>
+    where
+        F: FnOnce(&mut Self) -> Result<&mut Self>,
+    {
+        self.push_style(style)?;
+        let result = f(self).map(|_| ());
+        let restored = self.pop_style().map(|_| ());
+        result.and(restored)?;
+        Ok(self)
     }
 
     /// Smoothing mode
@@ -239,13 +902,21 @@ impl<D: Driver> Printer<D> {
 
     /// Line spacing
     pub fn line_spacing(&mut self, value: u8) -> Result<&mut Self> {
+        if self.state.line_spacing == Some(LineSpacing::Units(value)) {
+            return Ok(self);
+        }
         let cmd = self.protocol.line_spacing(value);
+        self.state.line_spacing = Some(LineSpacing::Units(value));
         self.command("line spacing", &[cmd])
     }
 
     /// Reset line spacing
     pub fn reset_line_spacing(&mut self) -> Result<&mut Self> {
+        if self.state.line_spacing == Some(LineSpacing::Default) {
+            return Ok(self);
+        }
         let cmd = self.protocol.reset_line_spacing();
+        self.state.line_spacing = Some(LineSpacing::Default);
         self.command("reset line spacing", &[cmd])
     }
 
@@ -255,25 +926,344 @@ impl<D: Driver> Printer<D> {
         self.command("upside-down mode", &[cmd])
     }
 
+    /// Kanji mode
+    ///
+    /// With an encoding, the Kanji code system is selected (Japanese models only) and the printer enters
+    /// Kanji mode: the text is then encoded with the double-byte encoding instead of the page code.
+    /// `None` leaves Kanji mode.
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     Printer::new(driver, Protocol::default(), None)
+    ///         .init()?
+    ///         .kanji_mode(Some(KanjiEncoding::ShiftJis))?
+    ///         .kanji_print_mode(true, true, false)?
+    ///         .writeln("ラーメン")?
+    ///         .kanji_print_mode(false, false, false)?
+    ///         .writeln("醤油ラーメン 800円")?
+    ///         .kanji_mode(None)?
+    ///         .print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn kanji_mode(&mut self, encoding: Option<KanjiEncoding>) -> Result<&mut Self> {
+        let cmd = match encoding {
+            Some(encoding) => {
+                self.require(Capability::Kanji)?;
+                match encoding.code_system() {
+                    Some(system) => vec![self.protocol.kanji_code_system(system), self.protocol.kanji_mode(true)],
+                    None => vec![self.protocol.kanji_mode(true)],
+                }
+            }
+            None => vec![self.protocol.kanji_mode(false)],
+        };
+        self.kanji_encoding = encoding;
+
+        self.command("Kanji mode", &cmd)
+    }
+
+    /// Kanji character code system (Japanese models)
+    pub fn kanji_code_system(&mut self, system: KanjiCodeSystem) -> Result<&mut Self> {
+        self.require(Capability::Kanji)?;
+        let cmd = self.protocol.kanji_code_system(system);
+        self.command("Kanji code system", &[cmd])
+    }
+
+    /// Kanji print mode
+    pub fn kanji_print_mode(&mut self, double_width: bool, double_height: bool, underline: bool) -> Result<&mut Self> {
+        self.require(Capability::Kanji)?;
+        let cmd = self.protocol.kanji_print_mode(double_width, double_height, underline);
+        self.command("Kanji print mode", &[cmd])
+    }
+
+    /// Unicode mode
+    ///
+    /// When enabled, the printer is switched to the UTF-8 encoding (`FS ( C`) and the text is sent
+    /// in UTF-8 without page code lookup, the characters being printed with the prioritized fonts.
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     Printer::new(driver, Protocol::default(), None)
+    ///         .profile(Some(PrinterProfile::from(PrinterModel::EpsonTmM30)))
+    ///         .init()?
+    ///         .unicode_mode(true)?
+    ///         .font_priority(&[UnicodeFont::Japanese, UnicodeFont::SimplifiedChinese])?
+    ///         .writeln("Crème brûlée, ラーメン, 牛肉面")?
+    ///         .print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn unicode_mode(&mut self, enabled: bool) -> Result<&mut Self> {
+        self.require(Capability::Unicode)?;
+        let cmd = self.protocol.unicode_mode(enabled);
+        self.unicode_mode = enabled;
+
+        self.command("Unicode mode", &[cmd])
+    }
+
+    /// Font priorities of Unicode mode (first priority first, 2 fonts maximum)
+    pub fn font_priority(&mut self, fonts: &[UnicodeFont]) -> Result<&mut Self> {
+        self.require(Capability::Unicode)?;
+        let cmd = self.protocol.font_priority(fonts)?;
+        self.command("font priority", &cmd)
+    }
+
     /// Cash drawer
     pub fn cash_drawer(&mut self, pin: CashDrawer) -> Result<&mut Self> {
+        self.require(Capability::CashDrawer)?;
         let cmd = self.protocol.cash_drawer(pin);
         self.command("cash drawer", &[cmd])
     }
 
+    /// Text command in the current text mode
+    fn text_command(&self, text: &str) -> Result<Command> {
+        match (
+            self.unicode_mode,
+            self.kanji_encoding,
+            self.indic_script,
+            self.auto_page_code,
+        ) {
+            (true, _, _, _) => Ok(self.protocol.unicode_text(text)),
+            (false, Some(encoding), _, _) => self.protocol.kanji_text(text, encoding),
+            (false, None, Some(script), _) => self.protocol.indic_text(text, script),
+            (false, None, None, true) => {
+                self.protocol
+                    .text_auto_page_code(text, self.page_code, &self.available_page_codes())
+            }
+            (false, None, None, false) => self.protocol.text(text, self.page_code),
+        }
+    }
+
+    /// Text commands of the lines in visual order, right-to-left lines being right-aligned
+    ///
+    /// With `line_end`, the last line is ended by a line feed sent afterwards: the returned flag tells whether
+    /// it is right-aligned, the justification having to be restored after the line feed.
+    fn bidi_commands(&self, text: &str, line_end: bool) -> Result<(Vec<Command>, bool)> {
+        let page_code = self
+            .page_code
+            .filter(|_| !self.unicode_mode && self.kanji_encoding.is_none());
+        let mut cmd = vec![];
+        let mut aligned = false;
+        let mut lines = text.split_inclusive('\n').peekable();
+
+        while let Some(line) = lines.next() {
+            let (line, line_feed) = match line.strip_suffix('\n') {
+                Some(line) => (line, "\n"),
+                None => (line, ""),
+            };
+            let last = line_end && line_feed.is_empty() && lines.peek().is_none();
+            let (visual, rtl) = self.protocol.bidi_text(line, page_code)?;
+            let align = rtl && (!line_feed.is_empty() || last) && !self.justified;
+
+            if align {
+                cmd.push(self.protocol.justify(JustifyMode::RIGHT));
+            }
+            cmd.push(self.text_command(&format!("{visual}{line_feed}"))?);
+            if align && last {
+                aligned = true;
+            } else if align {
+                cmd.push(self.protocol.justify(JustifyMode::LEFT));
+            }
+        }
+
+        Ok((cmd, aligned))
+    }
+
     /// Text
+    ///
+    /// The control characters of the text are stripped or escaped (see `Printer::control_character_policy`).
     pub fn write(&mut self, text: &str) -> Result<&mut Self> {
-        let cmd = self.protocol.text(text, self.page_code)?;
-        self.command("text", &[cmd])
+        let text = self.protocol.sanitize(text);
+        self.write_sanitized(&text)
     }
 
     /// Text + Line feed
     pub fn writeln(&mut self, text: &str) -> Result<&mut Self> {
-        self.write(text)?.feed()
+        let text = self.protocol.sanitize(text);
+        if !self.bidi_mode {
+            return self.write_sanitized(&text)?.feed();
+        }
+
+        // A right-to-left line stays right-aligned until its line feed
+        let (cmd, aligned) = self.bidi_commands(&text, true)?;
+        self.command("text", &cmd)?.feed()?;
+        if aligned {
+            let cmd = self.protocol.justify(JustifyMode::LEFT);
+            self.command("text justify", &[cmd])?;
+        }
+        Ok(self)
+    }
+
+    /// Number of columns per line with the current font and character width
+    ///
+    /// The line is the print area (see `Printer::left_margin` and `Printer::print_area_width`).
+    /// Wide characters (CJK ideographs, Hangul...) take two columns (see `text_width`).
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     let mut printer = Printer::new(driver, Protocol::default(), None);
+    ///     printer.profile(Some(PrinterProfile::from(PrinterModel::EpsonTmT88)));
+    ///     assert_eq!(printer.columns(), 42);
+    ///
+    ///     printer.size(2, 2)?;
+    ///     assert_eq!(printer.columns(), 21);
+    ///     assert_eq!(printer.measure("Total"), 120);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn columns(&self) -> usize {
+        let columns = usize::from(self.font_profile().columns) / usize::from(self.character_scale());
+        columns.min(self.print_area() / self.character_width().max(1))
+    }
+
+    /// Width of the print area in dots, from the left margin
+    fn print_area(&self) -> usize {
+        let line = match &self.profile {
+            Some(profile) => usize::from(profile.dots_per_line()),
+            None => {
+                let font = self.font_profile();
+                usize::from(font.columns) * usize::from(font.width)
+            }
+        };
+        let available = line.saturating_sub(usize::from(self.state.left_margin.unwrap_or_default()));
+        self.state
+            .print_area_width
+            .map_or(available, |width| available.min(usize::from(width)))
+    }
+
+    /// Width of a text in dots with the current font and character width
+    pub fn measure(&self, text: &str) -> usize {
+        text_width(text) * self.character_width()
+    }
+
+    /// Character width multiplier of the current text size
+    fn character_scale(&self) -> u8 {
+        self.state.size.map_or(1, |(width, _)| width.max(1))
+    }
+
+    /// Width of a character column in dots with the current font, character width and spacing
+    fn character_width(&self) -> usize {
+        let spacing = self.horizontal_dots(self.state.character_spacing.unwrap_or_default());
+        (usize::from(self.font_profile().width) + spacing) * usize::from(self.character_scale())
+    }
+
+    /// Text word-wrapped into the columns of the line (see `wrap_text`), each line followed by a line feed
+    ///
+    /// Words longer than a line are broken, with a hyphen if `hyphenate` is set.
+    pub fn write_wrapped(&mut self, text: &str, hyphenate: bool) -> Result<&mut Self> {
+        let text = self.protocol.sanitize(text);
+        for line in wrap_text(&text, self.columns(), hyphenate) {
+            self.writeln(&line)?;
+        }
+        Ok(self)
+    }
+
+    /// Table row
+    ///
+    /// The cells are laid out in the columns of the table (see `Table::widths`) on the line of the current font
+    /// and character width, each line of the row being followed by a line feed.
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let table = Table::new(vec![
+    ///         Column::new(ColumnWidth::Fixed(3)).with_align(ColumnAlign::Right),
+    ///         Column::new(ColumnWidth::Proportional(1)),
+    ///         Column::new(ColumnWidth::Fixed(10)).with_align(ColumnAlign::Decimal(2)),
+    ///     ]);
+    ///
+    ///     let driver = ConsoleDriver::open(false);
+    ///     Printer::new(driver, Protocol::default(), Some(PageCode::PC858))
+    ///         .init()?
+    ///         .table_row(&table, &["2", "Crème brûlée", "12.50 €"])?
+    ///         .table_row(&table, &["10", "Espresso", "25 €"])?
+    ///         .print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn table_row(&mut self, table: &Table, cells: &[&str]) -> Result<&mut Self> {
+        let cells = cells
+            .iter()
+            .map(|cell| self.protocol.sanitize(cell))
+            .collect::<Vec<_>>();
+        let cells = cells.iter().map(|cell| cell.as_ref()).collect::<Vec<_>>();
+        let widths = table.widths(self.columns())?;
+
+        for line in table.lines(&widths, &cells)? {
+            let mut text = String::new();
+            let mut position = 0;
+
+            for segment in line {
+                if table.absolute_positioning() {
+                    let position = self.horizontal_units(segment.start * self.character_width())?;
+                    self.write_pending(&mut text)?;
+                    let cmd = self.protocol.absolute_position(position);
+                    self.command("absolute position", &[cmd])?;
+                } else {
+                    text.push_str(&" ".repeat(segment.start.saturating_sub(position)));
+                }
+
+                if segment.bold {
+                    self.write_pending(&mut text)?;
+                    self.push_style(Style::new().with_bold(true))?
+                        .write_sanitized(&segment.text)?
+                        .pop_style()?;
+                } else {
+                    text.push_str(&segment.text);
+                }
+                position = segment.start + text_width(&segment.text);
+            }
+
+            self.write_pending(&mut text)?;
+            self.feed()?;
+        }
+
+        Ok(self)
+    }
+
+    /// Write the text of a line being built, if any
+    fn write_pending(&mut self, text: &mut String) -> Result<()> {
+        if !text.is_empty() {
+            self.write_sanitized(&std::mem::take(text))?;
+        }
+        Ok(())
+    }
+
+    /// Text without control characters
+    fn write_sanitized(&mut self, text: &str) -> Result<&mut Self> {
+        let cmd = match self.bidi_mode {
+            true => self.bidi_commands(text, false)?.0,
+            false => vec![self.text_command(text)?],
+        };
+        self.command("text", &cmd)
     }
 
     /// Custom command
     ///
+    /// The command is sent as is, without updating the print state (see `Printer::state`).
+    ///
     /// ```rust
     /// use escpos::printer::Printer;
     /// use escpos::utils::*;
@@ -318,22 +1308,342 @@ impl<D: Driver> Printer<D> {
     ///     Ok(())
     /// }
     /// ```
-    pub fn custom_with_page_code(&mut self, cmd: &[u8], page_code: PageCode) -> Result<&mut Self> {
-        self.page_code(page_code)?;
-        self.command(
-            &format!("custom command width page code {}", page_code),
-            &[cmd.to_vec()],
-        )
+    pub fn custom_with_page_code(&mut self, cmd: &[u8], page_code: PageCode) -> Result<&mut Self> {
+        self.page_code(page_code)?;
+        self.command(
+            &format!("custom command width page code {}", page_code),
+            &[cmd.to_vec()],
+        )
+    }
+
+    /// Set horizontal and vertical motion units
+    pub fn motion_units(&mut self, x: u8, y: u8) -> Result<&mut Self> {
+        if self.state.motion_units == Some((x, y)) {
+            return Ok(self);
+        }
+        let cmd = self.protocol.motion_units(x, y);
+        self.state.motion_units = Some((x, y));
+        self.command("set motion units", &[cmd])
+    }
+
+    /// Horizontal motion units of a length in dots
+    ///
+    /// A unit is a dot with the default motion units, or `1 / x` inch after `Printer::motion_units`
+    /// (converted with the resolution of the profile).
+    fn horizontal_units(&self, dots: usize) -> Result<u16> {
+        let units = self.motion_units_of(dots, self.state.motion_units.map(|(x, _)| x));
+        u16::try_from(units).map_err(|_| PrinterError::Input(format!("invalid horizontal position: {units}")))
+    }
+
+    /// Vertical motion units of a length in dots (see `Printer::horizontal_units`)
+    fn vertical_units(&self, dots: usize) -> Result<u16> {
+        let units = self.motion_units_of(dots, self.state.motion_units.map(|(_, y)| y));
+        u16::try_from(units).map_err(|_| PrinterError::Input(format!("invalid vertical position: {units}")))
+    }
+
+    /// Motion units of a length in dots, a unit being `1 / unit` inch (a dot if `0` or unknown)
+    fn motion_units_of(&self, dots: usize, unit: Option<u8>) -> usize {
+        match (unit, &self.profile) {
+            (Some(unit), Some(profile)) if unit > 0 && profile.dpi() > 0 => {
+                let dpi = usize::from(profile.dpi());
+                (dots * usize::from(unit) + dpi / 2) / dpi
+            }
+            _ => dots,
+        }
+    }
+
+    /// Length in dots of horizontal motion units (see `Printer::horizontal_units`)
+    fn horizontal_dots(&self, units: u8) -> usize {
+        match (self.state.motion_units, &self.profile) {
+            (Some((x, _)), Some(profile)) if x > 0 && profile.dpi() > 0 => {
+                let x = usize::from(x);
+                (usize::from(units) * usize::from(profile.dpi()) + x / 2) / x
+            }
+            _ => usize::from(units),
+        }
+    }
+
+    /// Absolute horizontal print position (`ESC $`)
+    ///
+    /// The position is in horizontal motion units (see `Printer::motion_units`) from the beginning of the line,
+    /// or from the starting corner of the print area in page mode.
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     Printer::new(driver, Protocol::default(), None)
+    ///         .init()?
+    ///         .motion_units(180, 180)?
+    ///         .write("Total")?
+    ///         .position(360)?
+    ///         .writeln("12.50")?
+    ///         .tab_positions(&[8, 24])?
+    ///         .tab()?
+    ///         .write("2 x Espresso")?
+    ///         .tab()?
+    ///         .writeln("5.00")?
+    ///         .print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn position(&mut self, position: u16) -> Result<&mut Self> {
+        let cmd = self.protocol.absolute_position(position);
+        self.command("absolute position", &[cmd])
+    }
+
+    /// Relative horizontal print position (`ESC \\`), in horizontal motion units from the current position
+    pub fn relative_position(&mut self, offset: i16) -> Result<&mut Self> {
+        let cmd = self.protocol.relative_position(offset);
+        self.command("relative position", &[cmd])
+    }
+
+    /// Horizontal tab positions (`ESC D`), in characters from the beginning of the line
+    ///
+    /// The positions must be ascending (1 to 255, 32 maximum), no position clearing them.
+    pub fn tab_positions(&mut self, positions: &[u8]) -> Result<&mut Self> {
+        let cmd = self.protocol.tab_positions(positions)?;
+        self.command("tab positions", &[cmd])
+    }
+
+    /// Horizontal tab (`HT`), moving to the next tab position
+    pub fn tab(&mut self) -> Result<&mut Self> {
+        let cmd = self.protocol.tab();
+        self.command("horizontal tab", &[cmd])
+    }
+
+    /// Move to the beginning of the line (`GS T`), after printing the data of the line or discarding it
+    ///
+    /// Only available in standard mode.
+    pub fn line_start(&mut self, print: bool) -> Result<&mut Self> {
+        self.require_standard_mode("line start")?;
+        let cmd = self.protocol.line_start(print);
+        self.command("line start", &[cmd])
+    }
+
+    /// Left margin (`GS L`), in dots from the left edge of the printable area
+    ///
+    /// Only available in standard mode. The justification, `Printer::columns` and the tables use the print area
+    /// starting at the margin.
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     let mut printer = Printer::new(driver, Protocol::default(), None);
+    ///     printer
+    ///         .profile(Some(PrinterProfile::from(PrinterModel::EpsonTmT88)))
+    ///         .init()?
+    ///         .left_margin(48)?
+    ///         .print_area_width(384)?;
+    ///     assert_eq!(printer.columns(), 32);
+    ///
+    ///     printer.justify(JustifyMode::CENTER)?.writeln("Centered in the print area")?.print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn left_margin(&mut self, dots: u16) -> Result<&mut Self> {
+        self.require_standard_mode("left margin")?;
+        if self.state.left_margin == Some(dots) {
+            return Ok(self);
+        }
+        if let Some(profile) = &self.profile {
+            if dots >= profile.dots_per_line() {
+                return Err(PrinterError::Input(format!(
+                    "left margin outside of the paper: {dots} dots for {}",
+                    profile.dots_per_line()
+                )));
+            }
+        }
+
+        let cmd = self.protocol.left_margin(self.horizontal_units(usize::from(dots))?);
+        self.state.left_margin = Some(dots);
+        self.command("left margin", &[cmd])
+    }
+
+    /// Print area width (`GS W`), in dots from the left margin
+    ///
+    /// Only available in standard mode.
+    pub fn print_area_width(&mut self, dots: u16) -> Result<&mut Self> {
+        self.require_standard_mode("print area width")?;
+        if self.state.print_area_width == Some(dots) {
+            return Ok(self);
+        }
+        if let Some(profile) = &self.profile {
+            let right = u32::from(self.state.left_margin.unwrap_or_default()) + u32::from(dots);
+            if right > u32::from(profile.dots_per_line()) {
+                return Err(PrinterError::Input(format!(
+                    "print area wider than the paper: {right} dots for {}",
+                    profile.dots_per_line()
+                )));
+            }
+        }
+
+        let cmd = self
+            .protocol
+            .print_area_width(self.horizontal_units(usize::from(dots))?)?;
+        self.state.print_area_width = Some(dots);
+        self.command("print area width", &[cmd])
+    }
+
+    /// Error in page mode
+    fn require_standard_mode(&self, instruction: &str) -> Result<()> {
+        match self.page_mode {
+            Some(_) => Err(PrinterError::Input(format!(
+                "{instruction} is only available in standard mode"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Page mode settings, or an error in standard mode
+    fn require_page_mode(&self, instruction: &str) -> Result<PageMode> {
+        self.page_mode
+            .ok_or_else(|| PrinterError::Input(format!("{instruction} is only available in page mode")))
+    }
+
+    /// Page mode (`ESC L`)
+    ///
+    /// The data is laid out in the print area (see `Printer::page_area`) and printed all at once by
+    /// `Printer::print_page`, which allows to print rotated text or text over graphics.
+    ///
+    /// ```rust
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::{driver::*, errors::Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let driver = ConsoleDriver::open(false);
+    ///     Printer::new(driver, Protocol::default(), None)
+    ///         .init()?
+    ///         .page_mode()?
+    ///         .page_area(0, 0, 512, 400)?
+    ///         .page_direction(PageDirection::BottomToTop)?
+    ///         .page_position(0, 24)?
+    ///         .writeln("Shipping label")?
+    ///         .page_position(200, 24)?
+    ///         .writeln("Fragile")?
+    ///         .print_page(true)?
+    ///         .print_cut()?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn page_mode(&mut self) -> Result<&mut Self> {
+        self.require(Capability::PageMode)?;
+        let cmd = self.protocol.page_mode();
+        self.page_mode = Some(PageMode::default());
+        self.command("page mode", &[cmd])
     }
 
-    /// Set horizontal and vertical motion units
-    pub fn motion_units(&mut self, x: u8, y: u8) -> Result<&mut Self> {
-        let cmd = self.protocol.motion_units(x, y);
-        self.command("set motion units", &[cmd])
+    /// Standard mode (`ESC S`), the data of page mode being discarded
+    pub fn standard_mode(&mut self) -> Result<&mut Self> {
+        let cmd = self.protocol.standard_mode();
+        self.page_mode = None;
+        self.command("standard mode", &[cmd])
+    }
+
+    /// Print area in page mode (`ESC W`), from the upper left corner of the paper in dots
+    ///
+    /// The dots are converted to motion units (see `Printer::motion_units`) with the resolution of the profile.
+    pub fn page_area(&mut self, x: u16, y: u16, width: u16, height: u16) -> Result<&mut Self> {
+        let mut page_mode = self.require_page_mode("page area")?;
+        if let Some(profile) = &self.profile {
+            let right = u32::from(x) + u32::from(width);
+            if right > u32::from(profile.dots_per_line()) {
+                return Err(PrinterError::Input(format!(
+                    "page area wider than the paper: {right} dots for {}",
+                    profile.dots_per_line()
+                )));
+            }
+        }
+
+        let cmd = self.protocol.page_area(
+            self.horizontal_units(usize::from(x))?,
+            self.vertical_units(usize::from(y))?,
+            self.horizontal_units(usize::from(width))?,
+            self.vertical_units(usize::from(height))?,
+        )?;
+        page_mode.area = Some((x, y, width, height));
+        self.page_mode = Some(page_mode);
+        self.command("page area", &[cmd])
+    }
+
+    /// Print direction in page mode (`ESC T`)
+    pub fn page_direction(&mut self, direction: PageDirection) -> Result<&mut Self> {
+        let mut page_mode = self.require_page_mode("page direction")?;
+        let cmd = self.protocol.page_direction(direction);
+        page_mode.direction = direction;
+        self.page_mode = Some(page_mode);
+        self.command("page direction", &[cmd])
+    }
+
+    /// Print position in page mode (`ESC $`, `GS $`), in dots from the starting corner of the print direction
+    pub fn page_position(&mut self, x: u16, y: u16) -> Result<&mut Self> {
+        let page_mode = self.require_page_mode("page position")?;
+        if let Some((_, _, width, height)) = page_mode.area {
+            let (width, height) = match page_mode.direction.is_vertical() {
+                true => (height, width),
+                false => (width, height),
+            };
+            if x >= width || y >= height {
+                return Err(PrinterError::Input(format!(
+                    "page position ({x}, {y}) outside of the print area ({width} x {height} dots)"
+                )));
+            }
+        }
+
+        // The positions are along the paper feed for a vertical direction, in vertical motion units
+        let (x, y) = match page_mode.direction.is_vertical() {
+            true => (
+                self.vertical_units(usize::from(x))?,
+                self.horizontal_units(usize::from(y))?,
+            ),
+            false => (
+                self.horizontal_units(usize::from(x))?,
+                self.vertical_units(usize::from(y))?,
+            ),
+        };
+        let cmd = vec![self.protocol.absolute_position(x), self.protocol.vertical_position(y)];
+        self.command("page position", &cmd)
+    }
+
+    /// Move the vertical print position in page mode (`GS \\`), in dots from the current position
+    pub fn page_vertical_offset(&mut self, offset: i16) -> Result<&mut Self> {
+        let page_mode = self.require_page_mode("page vertical offset")?;
+        let dots = usize::from(offset.unsigned_abs());
+        let units = match page_mode.direction.is_vertical() {
+            true => self.horizontal_units(dots)?,
+            false => self.vertical_units(dots)?,
+        };
+        let units = i16::try_from(units)
+            .map(|units| units * offset.signum())
+            .map_err(|_| PrinterError::Input(format!("invalid vertical position: {units}")))?;
+        let cmd = self.protocol.relative_vertical_position(units);
+        self.command("page vertical offset", &[cmd])
+    }
+
+    /// Print the data of page mode, then return to standard mode (`FF`) or stay in page mode (`ESC FF`)
+    pub fn print_page(&mut self, exit: bool) -> Result<&mut Self> {
+        self.require_page_mode("print page")?;
+        let cmd = self.protocol.print_page(exit);
+        if exit {
+            self.page_mode = None;
+        }
+        self.command("print page", &[cmd])
     }
 
     /// Ask printer to send real-time status
     pub fn real_time_status(&mut self, status: RealTimeStatusRequest) -> Result<&mut Self> {
+        self.require(Capability::RealTimeStatus)?;
         let cmd = self.protocol.real_time_status(status);
         self.command("real-time status", &[cmd])
     }
@@ -352,6 +1662,7 @@ impl<D: Driver> Printer<D> {
     #[cfg(feature = "barcodes")]
     /// Print barcode
     fn barcode(&mut self, barcode: Barcode) -> Result<&mut Self> {
+        self.require(Capability::Barcodes)?;
         let commands = self.protocol.barcode(&barcode.data, barcode.system, barcode.option)?;
         self.command(&format!("print {} barcode", barcode.system), commands.as_slice())
     }
@@ -444,6 +1755,18 @@ impl<D: Driver> Printer<D> {
     /// Construct QR code
     fn qrcode_builder(&mut self, data: &str, option: Option<QRCodeOption>) -> Result<&mut Self> {
         let qrcode = QRCode::new(data, option)?;
+
+        if !self.supports(Capability::QRCode) {
+            #[cfg(feature = "graphics")]
+            if self.supports(Capability::BitImage) {
+                self.degrade(&Capability::QRCode.to_string(), &Capability::BitImage.to_string())?;
+                let cmd = self.protocol.qrcode_raster(&qrcode.data, qrcode.option)?;
+                return self.cancel_data()?.command("print qrcode as bit image", &[cmd]);
+            }
+
+            return Err(self.unsupported(&Capability::QRCode.to_string()));
+        }
+
         let commands = self.protocol.qrcode(&qrcode.data, qrcode.option)?;
         self.command("print qrcode", commands.as_slice())
     }
@@ -464,6 +1787,7 @@ impl<D: Driver> Printer<D> {
     /// Construct 2D GS1 DataBar with custom option
     pub fn gs1_databar_2d_option(&mut self, data: &str, option: GS1DataBar2DOption) -> Result<&mut Self> {
         let code = GS1DataBar2D::new(data, option)?;
+        self.require(Capability::GS1DataBar2D)?;
         let commands = self.protocol.gs1_databar_2d(&code.data, code.option)?;
         self.command("print 2D GS1 DataBar", commands.as_slice())
     }
@@ -478,6 +1802,7 @@ impl<D: Driver> Printer<D> {
     /// PDF417
     pub fn pdf417_option(&mut self, data: &str, option: Pdf417Option) -> Result<&mut Self> {
         let code = Pdf417::new(data, option);
+        self.require(Capability::Pdf417)?;
         let commands = self.protocol.pdf417(&code.data, code.option)?;
         self.command("print PDF417", commands.as_slice())
     }
@@ -493,6 +1818,7 @@ impl<D: Driver> Printer<D> {
     /// MaxiCode
     pub fn maxi_code_option(&mut self, data: &str, mode: MaxiCodeMode) -> Result<&mut Self> {
         let code = MaxiCode::new(data, mode);
+        self.require(Capability::MaxiCode)?;
         let commands = self.protocol.maxi_code(&code.data, code.mode)?;
         self.command("print MaxiCode", commands.as_slice())
     }
@@ -508,6 +1834,7 @@ impl<D: Driver> Printer<D> {
     /// DataMatrix
     pub fn data_matrix_option(&mut self, data: &str, option: DataMatrixOption) -> Result<&mut Self> {
         let code = DataMatrix::new(data, option);
+        self.require(Capability::DataMatrix)?;
         let commands = self.protocol.data_matrix(&code.data, code.option)?;
         self.command("print DataMatrix", commands.as_slice())
     }
@@ -523,6 +1850,7 @@ impl<D: Driver> Printer<D> {
     /// Aztec code
     pub fn aztec_option(&mut self, data: &str, option: AztecOption) -> Result<&mut Self> {
         let code = Aztec::new(data, option);
+        self.require(Capability::Aztec)?;
         let commands = self.protocol.aztec(&code.data, code.option)?;
         self.command("print Aztec", commands.as_slice())
     }
@@ -537,13 +1865,24 @@ impl<D: Driver> Printer<D> {
     #[cfg(feature = "graphics")]
     /// Print image
     pub fn bit_image_option(&mut self, path: &str, option: BitImageOption) -> Result<&mut Self> {
-        let cmd = self.protocol.cancel();
-        self.command("cancel data", &[cmd])?;
+        self.require(Capability::BitImage)?;
 
+        self.cancel_data()?;
         let cmd = self.protocol.bit_image(path, option)?;
         self.command("print bit image", &[cmd])
     }
 
+    #[cfg(feature = "graphics")]
+    /// Cancel the data of the print buffer before an image (`CAN`), except in page mode where it would also
+    /// clear the data of the page
+    fn cancel_data(&mut self) -> Result<&mut Self> {
+        if self.page_mode.is_some() {
+            return Ok(self);
+        }
+        let cmd = self.protocol.cancel();
+        self.command("cancel data", &[cmd])
+    }
+
     #[cfg(feature = "graphics")]
     /// Print image
     pub fn bit_image(&mut self, path: &str) -> Result<&mut Self> {
@@ -553,9 +1892,9 @@ impl<D: Driver> Printer<D> {
     #[cfg(feature = "graphics")]
     /// Print image
     pub fn bit_image_from_bytes_option(&mut self, bytes: &[u8], option: BitImageOption) -> Result<&mut Self> {
-        let cmd = self.protocol.cancel();
-        self.command("cancel data", &[cmd])?;
+        self.require(Capability::BitImage)?;
 
+        self.cancel_data()?;
         let cmd = self.protocol.bit_image_from_bytes(bytes, option)?;
         self.command("print bit image from bytes", &[cmd])
     }
@@ -602,4 +1941,912 @@ mod tests {
 
         assert_eq!(printer.instructions, expected);
     }
+
+    #[test]
+    fn test_profile_page_code() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(PageCode::WPC1252));
+        printer
+            .profile(Some(
+                PrinterProfile::new("Clone", 384, 203)
+                    .with_page_code(PageCode::PC437, 0)
+                    .with_page_code(PageCode::WPC1252, 71),
+            ))
+            .init()
+            .unwrap()
+            .page_code(PageCode::PC437)
+            .unwrap();
+
+        let expected = vec![
+            Instruction::new("initialization", &[vec![27, 64]], None),
+            Instruction::new("character page code", &[vec![27, 116, 71]], None),
+            Instruction::new("character page code", &[vec![27, 116, 0]], None),
+        ];
+
+        assert_eq!(printer.instructions, expected);
+    }
+
+    #[test]
+    fn test_capability_policy_strict() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        printer.profile(Some(PrinterProfile::from(PrinterModel::XprinterXp58)));
+
+        assert!(matches!(printer.font(Font::C), Err(PrinterError::Unsupported(_))));
+        assert!(printer.cut().is_err());
+        assert!(printer.partial_cut().is_err());
+        assert!(printer.page_code(PageCode::WPC1256).is_err());
+        assert!(printer.cash_drawer(CashDrawer::Pin2).is_err());
+        assert!(printer.font(Font::B).is_ok());
+        assert!(printer.page_code(PageCode::PC858).is_ok());
+        assert_eq!(printer.instructions.len(), 2);
+    }
+
+    #[test]
+    fn test_capability_policy_degrade() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        printer
+            .profile(Some(
+                PrinterProfile::new("Clone", 384, 203)
+                    .with_font(Font::A, 12, 24, 32)
+                    .with_capability(Capability::FullCut),
+            ))
+            .capability_policy(CapabilityPolicy::Degrade)
+            .font(Font::C)
+            .unwrap()
+            .partial_cut()
+            .unwrap();
+
+        let expected = vec![
+            Instruction::new("text font", &[vec![27, 77, 0]], None),
+            Instruction::new("full paper cut", &[vec![29, 86, 65, 0]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+
+        // No cutter and no fallback for the cash drawer
+        printer.profile(Some(PrinterProfile::new("Clone", 384, 203)));
+        assert!(printer.cut().is_ok());
+        assert_eq!(printer.instructions.len(), 2);
+        assert!(printer.cash_drawer(CashDrawer::Pin2).is_err());
+    }
+
+    #[cfg(all(feature = "codes_2d", feature = "graphics"))]
+    #[test]
+    fn test_capability_policy_qrcode_raster() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        printer.profile(Some(
+            PrinterProfile::new("Clone", 384, 203).with_capability(Capability::BitImage),
+        ));
+        assert!(printer.qrcode("test").is_err());
+
+        printer
+            .capability_policy(CapabilityPolicy::Degrade)
+            .qrcode("test")
+            .unwrap();
+
+        // Version 1 (21 modules) + quiet zone, 4 dots per module
+        assert_eq!(
+            printer.instructions[0],
+            Instruction::new("cancel data", &[vec![24]], None)
+        );
+        let cmd = printer.instructions[1].flatten_commands();
+        assert_eq!(&cmd[..8], &[29, 118, 48, 0, 15, 0, 116, 0]);
+        assert_eq!(cmd.len(), 8 + 15 * 116);
+    }
+
+    #[test]
+    fn test_auto_page_code() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(PageCode::PC858));
+        printer
+            .profile(Some(PrinterProfile::from(PrinterModel::StarTsp100)))
+            .auto_page_code(true)
+            .write("é Б")
+            .unwrap();
+
+        let expected = vec![Instruction::new(
+            "text",
+            &[vec![130, 32, 27, 116, 17, 129, 27, 116, 19]],
+            None,
+        )];
+        assert_eq!(printer.instructions, expected);
+    }
+
+    #[test]
+    fn test_kanji_mode() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(PageCode::PC437));
+        printer.profile(Some(PrinterProfile::from(PrinterModel::EpsonTmT88)));
+        assert!(matches!(
+            printer.kanji_mode(Some(KanjiEncoding::ShiftJis)),
+            Err(PrinterError::Unsupported(_))
+        ));
+
+        printer
+            .profile(None)
+            .kanji_mode(Some(KanjiEncoding::ShiftJis))
+            .unwrap()
+            .write("日本")
+            .unwrap()
+            .kanji_mode(None)
+            .unwrap()
+            .write("é")
+            .unwrap();
+
+        let expected = vec![
+            Instruction::new("Kanji mode", &[vec![28, 67, 1], vec![28, 38]], None),
+            Instruction::new("text", &[vec![0x93, 0xFA, 0x96, 0x7B]], None),
+            Instruction::new("Kanji mode", &[vec![28, 46]], None),
+            Instruction::new("text", &[vec![130]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+    }
+
+    #[test]
+    fn test_code_page() {
+        let driver = ConsoleDriver::open(false);
+        let code_page = CodePage::from(PageCode::PC866).with_number(59).with_char(0xFF, '₽');
+        let protocol = Protocol::default().with_code_page(code_page);
+        let mut printer = Printer::new(driver, protocol, Some(PageCode::PC866));
+        printer
+            .profile(Some(
+                PrinterProfile::new("Clone", 576, 203).with_page_code(PageCode::PC437, 0),
+            ))
+            .auto_page_code(true)
+            .init()
+            .unwrap()
+            .write("Я₽ é")
+            .unwrap();
+
+        let expected = vec![
+            Instruction::new("initialization", &[vec![27, 64]], None),
+            Instruction::new("character page code", &[vec![27, 116, 59]], None),
+            Instruction::new("text", &[vec![0x9F, 0xFF, b' ', 27, 116, 0, 130, 27, 116, 59]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+    }
+
+    #[test]
+    fn test_code_page_control_byte() {
+        let driver = ConsoleDriver::open(false);
+        let code_page = CodePage::from(PageCode::PC437).with_char(0x1B, '←');
+        let protocol = Protocol::default().with_code_page(code_page);
+        let mut printer = Printer::new(driver, protocol, Some(PageCode::PC437));
+        printer.write("←@").unwrap();
+
+        let bytes: Vec<u8> = printer.instructions.iter().flat_map(|i| i.flatten_commands()).collect();
+        assert!(!bytes.windows(2).any(|w| w == [27, 64]));
+    }
+
+    /// Untrusted texts trying to send commands
+    const HOSTILE_TEXTS: [&str; 12] = [
+        "\x1Bp\x00\x19\u{FA}",              // ESC p: cash drawer pulse
+        "\x1D\x56\x00",                     // GS V: paper cut
+        "\x1B@",                            // ESC @: initialization
+        "\x1B?\x0A\x00",                    // ESC ?: clear
+        "\x10\x14\x01\x00\x05",             // DLE DC4: real-time pulse
+        "\x1C&\x1C.",                       // FS &: Kanji mode
+        "\x18\x0C\x0D",                     // CAN, FF, CR
+        "\x1D(k\x03\x001P0",                // GS ( k: 2D code data
+        "John\x1Bt\x11Doe",                 // ESC t in a name
+        "\x00\x01\x02\x03\x04\x05\x06\x07", // NUL to BEL
+        "\x1B\x1B\x1B\x1B\x1B",             // repeated ESC
+        "Table 4 \u{1B}E\u{01}Bold",        // ESC E: emphasis
+    ];
+
+    /// Check that a text instruction contains no control byte except LF and HT
+    fn assert_sanitized(instruction: &Instruction, text: &str) {
+        for byte in instruction.flatten_commands() {
+            assert!(byte >= 0x20 || byte == b'\n' || byte == b'\t', "{text:?}: {byte:#04x}");
+        }
+    }
+
+    #[test]
+    fn test_hostile_text() {
+        let modes: [fn(&mut Printer<ConsoleDriver>); 5] = [
+            |_| (),
+            |printer| {
+                printer.auto_page_code(true);
+            },
+            |printer| {
+                printer.kanji_mode(Some(KanjiEncoding::ShiftJis)).unwrap();
+            },
+            |printer| {
+                printer.unicode_mode(true).unwrap();
+            },
+            |printer| {
+                printer.bidi_mode(true);
+            },
+        ];
+
+        for (i, mode) in modes.iter().enumerate() {
+            for text in HOSTILE_TEXTS {
+                let driver = ConsoleDriver::open(false);
+                let mut printer = Printer::new(driver, Protocol::default(), Some(PageCode::PC437));
+                mode(&mut printer);
+                let before = printer.instructions.len();
+
+                printer.write(text).unwrap().writeln(text).unwrap();
+
+                let texts = printer.instructions[before..]
+                    .iter()
+                    .filter(|instruction| instruction.name == "text")
+                    .collect::<Vec<_>>();
+                assert_eq!(texts.len(), 2, "mode {i}: {text:?}");
+                for instruction in texts {
+                    assert_sanitized(instruction, text);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_control_character_policy() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        printer
+            .write("a\x1Bp\tb\n")
+            .unwrap()
+            .control_character_policy(ControlCharacterPolicy::Escape)
+            .allowed_control_characters(&[])
+            .write("a\x1Bp\tb\n")
+            .unwrap()
+            .custom(&[0x1B, b'p', 0, 25, 250])
+            .unwrap();
+
+        let expected = vec![
+            Instruction::new("text", &[b"ap\tb\n".to_vec()], None),
+            Instruction::new("text", &[b"a^[p^Ib^J".to_vec()], None),
+            Instruction::new("custom command", &[vec![0x1B, b'p', 0, 25, 250]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+    }
+
+    #[test]
+    fn test_columns_and_measure() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        assert_eq!(printer.columns(), 48);
+        assert_eq!(printer.measure("北京 x2"), 84);
+
+        printer.profile(Some(PrinterProfile::from(PrinterModel::XprinterXp58)));
+        assert_eq!(printer.columns(), 32);
+
+        printer.font(Font::B).unwrap().size(2, 3).unwrap();
+        assert_eq!(printer.columns(), 21);
+        assert_eq!(printer.measure("北京 x2"), 126);
+
+        printer.reset_size().unwrap();
+        assert_eq!(printer.columns(), 42);
+
+        printer.init().unwrap();
+        assert_eq!(printer.columns(), 32);
+    }
+
+    #[test]
+    fn test_write_wrapped() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(PageCode::PC437));
+        printer
+            .profile(Some(PrinterProfile::new("Narrow", 240, 203).with_font(
+                Font::A,
+                12,
+                24,
+                20,
+            )))
+            .size(2, 1)
+            .unwrap()
+            .write_wrapped("Extra cheese\x1B and pepperoni", true)
+            .unwrap();
+
+        let expected = vec![
+            Instruction::new("text size", &[vec![29, 33, 16]], None),
+            Instruction::new("text", &[b"Extra".to_vec()], None),
+            Instruction::new("line feed", &[vec![27, 100, 1]], None),
+            Instruction::new("text", &[b"cheese and".to_vec()], None),
+            Instruction::new("line feed", &[vec![27, 100, 1]], None),
+            Instruction::new("text", &[b"pepperoni".to_vec()], None),
+            Instruction::new("line feed", &[vec![27, 100, 1]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+    }
+
+    #[test]
+    fn test_table_row() {
+        let table = Table::new(vec![
+            Column::new(ColumnWidth::Fixed(2)).with_align(ColumnAlign::Right),
+            Column::new(ColumnWidth::Proportional(1)),
+            Column::new(ColumnWidth::Fixed(6))
+                .with_align(ColumnAlign::Decimal(2))
+                .with_bold(true),
+        ]);
+        let profile = PrinterProfile::new("Narrow", 240, 203).with_font(Font::A, 12, 24, 20);
+
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver.clone(), Protocol::default(), Some(PageCode::PC437));
+        printer
+            .profile(Some(profile.clone()))
+            .table_row(&table, &["1", "Large apple pie", "4.5"])
+            .unwrap();
+
+        let expected = vec![
+            Instruction::new("text", &[b" 1 Large        ".to_vec()], None),
+            Instruction::new("text bold", &[vec![27, 69, 1]], None),
+            Instruction::new("text", &[b"4.5".to_vec()], None),
+            Instruction::new("text bold", &[vec![27, 69, 0]], None),
+            Instruction::new("line feed", &[vec![27, 100, 1]], None),
+            Instruction::new("text", &[b"   apple pie".to_vec()], None),
+            Instruction::new("line feed", &[vec![27, 100, 1]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+
+        let mut printer = Printer::new(driver, Protocol::default(), Some(PageCode::PC437));
+        printer
+            .profile(Some(profile))
+            .table_row(&table.with_absolute_positioning(true), &["1", "Large apple pie", "4.5"])
+            .unwrap();
+
+        let expected = vec![
+            Instruction::new("absolute position", &[vec![27, 36, 12, 0]], None),
+            Instruction::new("text", &[b"1".to_vec()], None),
+            Instruction::new("absolute position", &[vec![27, 36, 36, 0]], None),
+            Instruction::new("text", &[b"Large".to_vec()], None),
+            Instruction::new("absolute position", &[vec![27, 36, 192, 0]], None),
+            Instruction::new("text bold", &[vec![27, 69, 1]], None),
+            Instruction::new("text", &[b"4.5".to_vec()], None),
+            Instruction::new("text bold", &[vec![27, 69, 0]], None),
+            Instruction::new("line feed", &[vec![27, 100, 1]], None),
+            Instruction::new("absolute position", &[vec![27, 36, 36, 0]], None),
+            Instruction::new("text", &[b"apple pie".to_vec()], None),
+            Instruction::new("line feed", &[vec![27, 100, 1]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+    }
+
+    #[test]
+    fn test_style_stack() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(PageCode::PC437));
+        printer
+            .push_style(Style::new().with_bold(true).with_size(2, 2))
+            .unwrap()
+            .write("A")
+            .unwrap()
+            .push_style(Style::new().with_bold(true).with_underline(UnderlineMode::Single))
+            .unwrap()
+            .pop_style()
+            .unwrap()
+            .pop_style()
+            .unwrap();
+
+        let expected = vec![
+            Instruction::new("text bold", &[vec![27, 69, 1]], None),
+            Instruction::new("text size", &[vec![29, 33, 17]], None),
+            Instruction::new("text", &[b"A".to_vec()], None),
+            Instruction::new("text underline", &[vec![27, 45, 1]], None),
+            Instruction::new("text underline", &[vec![27, 45, 0]], None),
+            Instruction::new("text bold", &[vec![27, 69, 0]], None),
+            Instruction::new("text size", &[vec![29, 33, 0]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+        assert_eq!(
+            printer.pop_style().err().map(|e| e.to_string()),
+            Some("Input error: no style to pop".to_string())
+        );
+    }
+
+    #[test]
+    fn test_with_style() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(PageCode::PC437));
+        printer
+            .with_style(Style::new().with_justify(JustifyMode::CENTER), |printer| {
+                printer.writeln("Title")
+            })
+            .unwrap();
+        assert!(printer
+            .with_style(Style::new().with_reverse(true), |printer| printer.size(9, 1))
+            .is_err());
+
+        let expected = vec![
+            Instruction::new("text justify", &[vec![27, 97, 1]], None),
+            Instruction::new("text", &[b"Title".to_vec()], None),
+            Instruction::new("line feed", &[vec![27, 100, 1]], None),
+            Instruction::new("text justify", &[vec![27, 97, 0]], None),
+            Instruction::new("text reverse colour", &[vec![29, 66, 1]], None),
+            Instruction::new("text reverse colour", &[vec![29, 66, 0]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+    }
+
+    #[test]
+    fn test_failing_style() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        printer.init().unwrap();
+        assert!(printer
+            .with_style(Style::new().with_bold(true).with_size(9, 1), |printer| printer
+                .writeln("Title"))
+            .is_err());
+        assert_eq!(printer.state().bold(), Some(false));
+        assert!(printer.styles.is_empty());
+
+        // The error of the closure is returned before the one of the restoration
+        let result = printer.with_style(Style::new().with_reverse(true), |printer| {
+            printer.pop_style()?.size(9, 1)
+        });
+        assert_eq!(
+            result.err().map(|e| e.to_string()),
+            Some("Input error: invalid text_size width: 9".to_string())
+        );
+
+        let expected = vec![
+            Instruction::new("initialization", &[vec![27, 64]], None),
+            Instruction::new("text bold", &[vec![27, 69, 1]], None),
+            Instruction::new("text bold", &[vec![27, 69, 0]], None),
+            Instruction::new("text reverse colour", &[vec![29, 66, 1]], None),
+            Instruction::new("text reverse colour", &[vec![29, 66, 0]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+
+        // The initialization clears the style stack
+        printer
+            .push_style(Style::new().with_bold(true))
+            .unwrap()
+            .init()
+            .unwrap();
+        assert!(printer.styles.is_empty());
+        assert!(printer.pop_style().is_err());
+    }
+
+    #[test]
+    fn test_print_state() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(PageCode::PC437));
+        printer
+            .bold(false)
+            .unwrap()
+            .init()
+            .unwrap()
+            .bold(true)
+            .unwrap()
+            .bold(true)
+            .unwrap()
+            .justify(JustifyMode::LEFT)
+            .unwrap()
+            .reset_size()
+            .unwrap()
+            .line_spacing(40)
+            .unwrap()
+            .line_spacing(40)
+            .unwrap()
+            .page_code(PageCode::PC437)
+            .unwrap();
+
+        let expected = vec![
+            Instruction::new("text bold", &[vec![27, 69, 0]], None),
+            Instruction::new("initialization", &[vec![27, 64]], None),
+            Instruction::new("character page code", &[vec![27, 116, 0]], None),
+            Instruction::new("text bold", &[vec![27, 69, 1]], None),
+            Instruction::new("line spacing", &[vec![27, 51, 40]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+        assert_eq!(printer.state().bold(), Some(true));
+        assert_eq!(printer.state().line_spacing(), Some(LineSpacing::Units(40)));
+        assert_eq!(printer.state().page_code(), Some(PageCode::PC437));
+
+        printer.instructions.clear();
+        printer.reset().unwrap().restore_state().unwrap();
+
+        let expected = vec![
+            Instruction::new("reset", &[vec![27, 63, 10, 0]], None),
+            Instruction::new("text font", &[vec![27, 77, 0]], None),
+            Instruction::new("text bold", &[vec![27, 69, 1]], None),
+            Instruction::new("text underline", &[vec![27, 45, 0]], None),
+            Instruction::new("text size", &[vec![29, 33, 0]], None),
+            Instruction::new("text justify", &[vec![27, 97, 0]], None),
+            Instruction::new("text reverse colour", &[vec![29, 66, 0]], None),
+            Instruction::new("line spacing", &[vec![27, 51, 40]], None),
+            Instruction::new("set motion units", &[vec![29, 80, 0, 0]], None),
+            Instruction::new("left margin", &[vec![29, 76, 0, 0]], None),
+            Instruction::new("character spacing", &[vec![27, 32, 0]], None),
+            Instruction::new("international character set", &[vec![27, 82, 0]], None),
+            Instruction::new("character page code", &[vec![27, 116, 0]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+
+        // The print state is kept if a setting cannot be sent
+        let state = printer.state().clone();
+        printer.profile(Some(
+            PrinterProfile::new("Clone", 576, 203).with_page_code(PageCode::PC866, 17),
+        ));
+        assert!(printer.restore_state().is_err());
+        assert_eq!(printer.state(), &state);
+    }
+
+    #[test]
+    fn test_horizontal_position() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        printer
+            .profile(Some(PrinterProfile::new("Receipt", 576, 203)))
+            .init()
+            .unwrap();
+        assert_eq!(printer.horizontal_units(406).unwrap(), 406);
+
+        printer
+            .motion_units(180, 180)
+            .unwrap()
+            .motion_units(180, 180)
+            .unwrap()
+            .position(360)
+            .unwrap()
+            .relative_position(-12)
+            .unwrap()
+            .tab_positions(&[8])
+            .unwrap()
+            .tab()
+            .unwrap()
+            .line_start(false)
+            .unwrap();
+        assert_eq!(printer.horizontal_units(406).unwrap(), 360);
+        assert!(printer.horizontal_units(100_000).is_err());
+        assert!(printer.tab_positions(&[8, 8]).is_err());
+
+        let expected = vec![
+            Instruction::new("initialization", &[vec![27, 64]], None),
+            Instruction::new("set motion units", &[vec![29, 80, 180, 180]], None),
+            Instruction::new("absolute position", &[vec![27, 36, 104, 1]], None),
+            Instruction::new("relative position", &[vec![27, 92, 244, 255]], None),
+            Instruction::new("tab positions", &[vec![27, 68, 8, 0]], None),
+            Instruction::new("horizontal tab", &[vec![9]], None),
+            Instruction::new("line start", &[vec![29, 84, 0]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+
+        printer.page_mode = Some(PageMode::default());
+        assert!(printer.line_start(true).is_err());
+    }
+
+    #[test]
+    fn test_page_mode() {
+        let profile = PrinterProfile::new("Label", 384, 203);
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(PageCode::PC437));
+        printer.profile(Some(profile.clone()));
+        assert!(matches!(printer.page_mode(), Err(PrinterError::Unsupported(_))));
+
+        printer.profile(Some(profile.with_capability(Capability::PageMode)));
+        assert_eq!(
+            printer.page_area(0, 0, 384, 200).err().map(|e| e.to_string()),
+            Some("Input error: page area is only available in page mode".to_string())
+        );
+
+        printer
+            .page_mode()
+            .unwrap()
+            .page_area(0, 0, 384, 200)
+            .unwrap()
+            .page_direction(PageDirection::TopToBottom)
+            .unwrap()
+            .page_position(180, 300)
+            .unwrap()
+            .write("A")
+            .unwrap()
+            .page_vertical_offset(-24)
+            .unwrap()
+            .print_page(false)
+            .unwrap();
+        assert!(printer.page_area(10, 0, 384, 200).is_err());
+        assert_eq!(
+            printer.page_position(200, 0).err().map(|e| e.to_string()),
+            Some("Input error: page position (200, 0) outside of the print area (200 x 384 dots)".to_string())
+        );
+
+        printer.print_page(true).unwrap();
+        assert!(printer.print_page(true).is_err());
+
+        let expected = vec![
+            Instruction::new("page mode", &[vec![27, 76]], None),
+            Instruction::new("page area", &[vec![27, 87, 0, 0, 0, 0, 128, 1, 200, 0]], None),
+            Instruction::new("page direction", &[vec![27, 84, 3]], None),
+            Instruction::new("page position", &[vec![27, 36, 180, 0], vec![29, 36, 44, 1]], None),
+            Instruction::new("text", &[b"A".to_vec()], None),
+            Instruction::new("page vertical offset", &[vec![29, 92, 232, 255]], None),
+            Instruction::new("print page", &[vec![27, 12]], None),
+            Instruction::new("print page", &[vec![12]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+
+        // The positions in dots are converted to motion units
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        printer
+            .profile(Some(
+                PrinterProfile::new("Label", 576, 203).with_capability(Capability::PageMode),
+            ))
+            .init()
+            .unwrap()
+            .motion_units(180, 90)
+            .unwrap()
+            .page_mode()
+            .unwrap()
+            .page_area(0, 0, 406, 203)
+            .unwrap()
+            .page_position(203, 100)
+            .unwrap()
+            .page_direction(PageDirection::BottomToTop)
+            .unwrap()
+            .page_position(100, 203)
+            .unwrap()
+            .page_vertical_offset(-203)
+            .unwrap();
+
+        let expected = vec![
+            Instruction::new("page area", &[vec![27, 87, 0, 0, 0, 0, 104, 1, 90, 0]], None),
+            Instruction::new("page position", &[vec![27, 36, 180, 0], vec![29, 36, 44, 0]], None),
+            Instruction::new("page direction", &[vec![27, 84, 1]], None),
+            Instruction::new("page position", &[vec![27, 36, 44, 0], vec![29, 36, 180, 0]], None),
+            Instruction::new("page vertical offset", &[vec![29, 92, 76, 255]], None),
+        ];
+        assert_eq!(printer.instructions[3..], expected);
+    }
+
+    #[test]
+    fn test_print_area() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        printer
+            .profile(Some(PrinterProfile::new("Receipt", 576, 203)))
+            .init()
+            .unwrap();
+        assert_eq!(printer.state().print_area_width(), Some(576));
+        assert_eq!(printer.columns(), 48);
+
+        printer
+            .left_margin(96)
+            .unwrap()
+            .left_margin(96)
+            .unwrap()
+            .print_area_width(360)
+            .unwrap()
+            .motion_units(180, 180)
+            .unwrap()
+            .print_area_width(406)
+            .unwrap();
+        assert_eq!(printer.state().left_margin(), Some(96));
+        assert_eq!(printer.state().print_area_width(), Some(406));
+        assert_eq!(printer.columns(), 33);
+        printer.size(2, 1).unwrap();
+        assert_eq!(printer.columns(), 16);
+
+        assert!(printer.left_margin(576).is_err());
+        assert_eq!(
+            printer.print_area_width(500).err().map(|e| e.to_string()),
+            Some("Input error: print area wider than the paper: 596 dots for 576".to_string())
+        );
+
+        let expected = vec![
+            Instruction::new("initialization", &[vec![27, 64]], None),
+            Instruction::new("left margin", &[vec![29, 76, 96, 0]], None),
+            Instruction::new("print area width", &[vec![29, 87, 104, 1]], None),
+            Instruction::new("set motion units", &[vec![29, 80, 180, 180]], None),
+            Instruction::new("print area width", &[vec![29, 87, 104, 1]], None),
+            Instruction::new("text size", &[vec![29, 33, 16]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+
+        printer.page_mode = Some(PageMode::default());
+        assert!(printer.left_margin(0).is_err());
+    }
+
+    #[test]
+    fn test_print_mode() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        printer
+            .profile(Some(PrinterProfile::from(PrinterModel::EpsonTmT88)))
+            .init()
+            .unwrap()
+            .print_mode(
+                PrintMode::new()
+                    .with_font(Font::B)
+                    .with_bold(true)
+                    .with_double_width(true)
+                    .with_underline(true),
+            )
+            .unwrap();
+        assert_eq!(printer.state().font(), Some(Font::B));
+        assert_eq!(printer.state().bold(), Some(true));
+        assert_eq!(printer.state().underline(), Some(UnderlineMode::Single));
+        assert_eq!(printer.state().size(), Some((2, 1)));
+        assert_eq!(printer.columns(), 28);
+
+        printer
+            .bold(true)
+            .unwrap()
+            .print_mode(PrintMode::new())
+            .unwrap()
+            .character_spacing(4)
+            .unwrap()
+            .character_spacing(4)
+            .unwrap();
+        assert_eq!(printer.state().character_spacing(), Some(4));
+        assert_eq!(printer.columns(), 32);
+        assert_eq!(printer.measure("Total"), 80);
+
+        printer
+            .unidirectional(true)
+            .unwrap()
+            .character_colour(TextColour::Colour2)
+            .unwrap()
+            .background_colour(TextColour::None)
+            .unwrap()
+            .shading(false, TextColour::None)
+            .unwrap();
+        assert!(printer.print_mode(PrintMode::new().with_font(Font::C)).is_err());
+
+        let expected = vec![
+            Instruction::new("initialization", &[vec![27, 64]], None),
+            Instruction::new("text print mode", &[vec![27, 33, 169]], None),
+            Instruction::new("text print mode", &[vec![27, 33, 0]], None),
+            Instruction::new("character spacing", &[vec![27, 32, 4]], None),
+            Instruction::new("unidirectional printing", &[vec![27, 85, 1]], None),
+            Instruction::new("character colour", &[vec![29, 40, 78, 2, 0, 48, 50]], None),
+            Instruction::new("background colour", &[vec![29, 40, 78, 2, 0, 49, 48]], None),
+            Instruction::new("character shading", &[vec![29, 40, 78, 3, 0, 50, 48, 48]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+    }
+
+    #[test]
+    fn test_character_set() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(PageCode::PC437));
+        printer
+            .character_set(CharacterSet::Germany)
+            .unwrap()
+            .write("Größe")
+            .unwrap()
+            .init()
+            .unwrap()
+            .write("ö")
+            .unwrap();
+
+        let expected = vec![
+            Instruction::new("international character set", &[vec![27, 82, 2]], None),
+            Instruction::new("text", &[vec![b'G', b'r', 0x7C, 0x7E, b'e']], None),
+            Instruction::new("initialization", &[vec![27, 64]], None),
+            Instruction::new("character page code", &[vec![27, 116, 0]], None),
+            Instruction::new("text", &[vec![148]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+    }
+
+    #[test]
+    fn test_indic_character_set() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(PageCode::PC437));
+        printer
+            .character_set(CharacterSet::IndiaDevanagari)
+            .unwrap()
+            .write("नमस्ते")
+            .unwrap()
+            .character_set(CharacterSet::USA)
+            .unwrap()
+            .write("é")
+            .unwrap();
+
+        let expected = vec![
+            Instruction::new("international character set", &[vec![27, 82, 66]], None),
+            Instruction::new("text", &[vec![0xC6, 0xCC, 0xD7, 0xE8, 0xC2, 0xE1]], None),
+            Instruction::new("international character set", &[vec![27, 82, 0]], None),
+            Instruction::new("text", &[vec![130]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+    }
+
+    #[test]
+    fn test_unicode_mode() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(PageCode::PC858));
+        printer.profile(Some(PrinterProfile::from(PrinterModel::StarTsp100)));
+        assert!(matches!(printer.unicode_mode(true), Err(PrinterError::Unsupported(_))));
+
+        printer
+            .profile(Some(PrinterProfile::from(PrinterModel::EpsonTmM30)))
+            .unicode_mode(true)
+            .unwrap()
+            .write("é€日")
+            .unwrap()
+            .unicode_mode(false)
+            .unwrap()
+            .write("é€")
+            .unwrap();
+
+        let expected = vec![
+            Instruction::new("Unicode mode", &[vec![28, 40, 67, 2, 0, 48, 2]], None),
+            Instruction::new("text", &["é€日".as_bytes().to_vec()], None),
+            Instruction::new("Unicode mode", &[vec![28, 40, 67, 2, 0, 48, 1]], None),
+            Instruction::new("text", &[vec![130, 213]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+    }
+
+    #[test]
+    fn test_bidi_mode() {
+        let driver = ConsoleDriver::open(false);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(PageCode::PC864));
+        printer
+            .bidi_mode(true)
+            .writeln("سلام 10")
+            .unwrap()
+            .write("a")
+            .unwrap()
+            .justify(JustifyMode::CENTER)
+            .unwrap()
+            .writeln("سلام")
+            .unwrap();
+
+        let expected = vec![
+            Instruction::new(
+                "text",
+                &[vec![27, 97, 2], vec![b'1', b'0', b' ', 0xEF, 0x9E, 0xD3]],
+                None,
+            ),
+            Instruction::new("line feed", &[vec![27, 100, 1]], None),
+            Instruction::new("text justify", &[vec![27, 97, 0]], None),
+            Instruction::new("text", &[vec![b'a']], None),
+            Instruction::new("text justify", &[vec![27, 97, 1]], None),
+            Instruction::new("text", &[vec![0xEF, 0x9E, 0xD3]], None),
+            Instruction::new("line feed", &[vec![27, 100, 1]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+
+        // Embedded line feeds are sent in the text in both modes
+        printer.instructions.clear();
+        printer
+            .justify(JustifyMode::LEFT)
+            .unwrap()
+            .bidi_mode(false)
+            .writeln("a\nb")
+            .unwrap()
+            .bidi_mode(true)
+            .writeln("a\nb")
+            .unwrap();
+
+        let expected = vec![
+            Instruction::new("text justify", &[vec![27, 97, 0]], None),
+            Instruction::new("text", &[b"a\nb".to_vec()], None),
+            Instruction::new("line feed", &[vec![27, 100, 1]], None),
+            Instruction::new("text", &[b"a\n".to_vec(), b"b".to_vec()], None),
+            Instruction::new("line feed", &[vec![27, 100, 1]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+
+        // A style justification does not disable the alignment of the lines written after it
+        printer.instructions.clear();
+        printer
+            .init()
+            .unwrap()
+            .with_style(Style::new().with_justify(JustifyMode::CENTER), |printer| {
+                printer.write("a")
+            })
+            .unwrap()
+            .writeln("سلام")
+            .unwrap();
+        let expected = vec![
+            Instruction::new("initialization", &[vec![27, 64]], None),
+            Instruction::new("character page code", &[vec![27, 116, 37]], None),
+            Instruction::new("text justify", &[vec![27, 97, 1]], None),
+            Instruction::new("text", &[vec![b'a']], None),
+            Instruction::new("text justify", &[vec![27, 97, 0]], None),
+            Instruction::new("text", &[vec![27, 97, 2], vec![0xEF, 0x9E, 0xD3]], None),
+            Instruction::new("line feed", &[vec![27, 100, 1]], None),
+            Instruction::new("text justify", &[vec![27, 97, 0]], None),
+        ];
+        assert_eq!(printer.instructions, expected);
+    }
 }